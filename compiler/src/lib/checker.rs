@@ -1,14 +1,15 @@
 use colored::*;
-use maplit::hashset;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use crate::issue::Issue;
 use crate::source_file::Span;
-use crate::{ChildRules, ComponentDef, Ctx, Expr, ExprValue};
+use crate::{BinaryOp, ChildRules, ComponentDef, Ctx, EnumDef, Expr, ExprValue, MatchArm, MAX_INT, PropDef, UnaryOp};
 
 use super::{
+	ProvideDecl,
 	parser::Component as ParserComponent,
 	parser::Content as ParserContent,
 	parser::Element as ParserElement,
@@ -36,17 +37,38 @@ pub struct Element {
 	pub tag: ElementTag,
 	pub data: Option<CheckedExpr>,
 	pub condition: Option<CheckedExpr>,
+	pub alternatives: Vec<Alternative>,
 	pub repeater: Option<CheckedRepeater>,
 	pub props: HashMap<String, CheckedExpr>,
 	pub presets: HashMap<String, CheckedExpr>,
+	pub slots: HashMap<String, Element>,
+	pub events: HashMap<String, CheckedExpr>,
 	pub children: Vec<Content>,
 }
 
+#[derive(Debug)]
+pub struct Alternative {
+	pub condition: Option<CheckedExpr>,
+	pub element: Element,
+}
+
 #[derive(Debug)]
 pub struct Component {
 	pub name: String,
 	pub root: Element,
 	pub props: Vec<PropDecl>,
+	pub computed: HashMap<String, CheckedExpr>,
+	pub provides: Vec<ProvideDecl>,
+	pub injects: Vec<String>,
+	pub enums: Vec<Rc<EnumDef>>,
+	pub children: Option<Children>,
+	pub child_provides: Vec<String>,
+	pub slots: Vec<String>,
+	/// the events of the root element, which the component exposes as its own
+	pub events: Vec<String>,
+	/// imported components by the name they're used under, with the name of their class and the
+	/// enums their file exports, whose values can be assigned to the component's properties
+	pub imports: Vec<(String, String, Vec<String>)>,
 }
 
 #[derive(Debug, Clone)]
@@ -72,19 +94,380 @@ pub struct CheckedRepeater {
 	pub index: Option<String>,
 	pub item: Option<String>,
 	pub item_type: Type,
-	pub root_type: String,
+}
+
+fn check_prop_decl(scope: &Module, unchecked: &PropDecl) -> Result<(PropDecl, Option<CheckedExpr>), ()> {
+	let checked = if let Some(default) = &unchecked.default {
+		let checked = type_check(scope, default, &unchecked.prop_type)?;
+		if !unchecked.is_readonly && !checked.bindings.is_empty() {
+			let message = format!(
+				"default value of `{}` cannot depend on other properties (declare it `readonly` to compute it)",
+				unchecked.name,
+			);
+			Issue::error(message, default.span.clone()).with_code("invalid-default").report();
+			return Err(());
+		}
+		Some(checked)
+	} else {
+		None
+	};
+
+	let default = checked.as_ref().map(|e| e.expr.clone());
+	Ok((PropDecl { default, ..unchecked.clone() }, checked))
+}
+
+fn check_computed_cycles(props: &[PropDecl], computed: &HashMap<String, CheckedExpr>) -> Result<(), ()> {
+	fn visit<'a>(
+		name: &'a str,
+		dependencies: &HashMap<&'a str, Vec<&'a str>>,
+		visiting: &mut Vec<&'a str>,
+	) -> Option<Vec<&'a str>> {
+		if let Some(i) = visiting.iter().position(|e| *e == name) {
+			return Some(visiting[i..].to_vec());
+		}
+		visiting.push(name);
+		for dependency in dependencies.get(name).iter().flat_map(|e| e.iter()) {
+			if let Some(cycle) = visit(dependency, dependencies, visiting) {
+				return Some(cycle);
+			}
+		}
+		visiting.pop();
+		None
+	}
+
+	// object-typed props are computed field by field, so track dependencies between top-level props
+	let mut dependencies: HashMap<&str, Vec<&str>> = HashMap::new();
+	for (name, expr) in computed.iter() {
+		let name = name.split('.').next().unwrap();
+		let entry = dependencies.entry(name).or_default();
+		entry.extend(expr.bindings.iter().map(|e| e.split('.').next().unwrap()));
+	}
+
+	for prop in props.iter().filter(|e| e.is_readonly) {
+		if let Some(cycle) = visit(&prop.name, &dependencies, &mut Vec::new()) {
+			if cycle[0] != prop.name {
+				continue;
+			}
+			let cycle: Vec<_> = cycle.iter().chain(cycle.first()).map(|e| format!("`{e}`")).collect();
+			let message = format!("readonly property `{}` depends on itself ({})", prop.name, cycle.join(" -> "));
+			Issue::error(message, prop.span.clone()).with_code("dependency-cycle").report();
+			return Err(());
+		}
+	}
+	Ok(())
+}
+
+/// Whether an element with `tag` can be held by a `Component<bound>` property. Imported components
+/// count as the element at their root, since that's what they render.
+fn fits_element_bound(scope: &Module, tag: &ElementTag, bound: &Type) -> bool {
+	let mut tag = tag;
+	loop {
+		let component = tag.import_path.as_ref().map(|path| &scope.components[path]);
+		let name = component.map_or(tag.path.last().unwrap(), |e| &e.name);
+		if *name == bound.name() {
+			return true;
+		}
+		match component {
+			Some(component) => tag = &component.root.tag,
+			None => return false,
+		}
+	}
+}
+
+/// whether a value of type `available` has everything that a consumer of type `required` reads
+fn type_fits(required: &Type, available: &Type) -> bool {
+	match (required, available) {
+		(Type::Object(required), Type::Object(available)) => required.iter().all(|(k, v)| {
+			available.get(k).is_some_and(|e| type_fits(&v.prop_type, &e.prop_type))
+		}),
+		(Type::Element(None), Type::Element(..)) => true,
+		_ => required == available,
+	}
+}
+
+fn check_inject(scope: &Module, prop: &PropDecl, key: &str) -> Result<(), ()> {
+	let key_type = scope.context_key(key, &prop.span)?;
+	if !type_fits(&prop.prop_type, key_type) {
+		let message = format!(
+			"`{}` (type `{}`) cannot be injected from `{}` (type `{}`)",
+			prop.name,
+			prop.prop_type.name().cyan(),
+			key,
+			key_type.name().cyan(),
+		);
+		Issue::error(message, prop.span.clone()).with_code("type-mismatch").report();
+		return Err(());
+	}
+	Ok(())
+}
+
+fn check_provide(scope: &Module, provide: &ProvideDecl) -> Result<(), ()> {
+	let key_type = scope.context_key(&provide.key, &provide.span)?;
+	let prop_type = match scope.lookup(&provide.path, &provide.span)? {
+		Some((Ctx::Component, t)) => t,
+		_ => {
+			let message = format!("`{}` is not a property of this component", provide.path.join("."));
+			Issue::error(message, provide.span.clone()).with_code("unknown-property").report();
+			return Err(());
+		}
+	};
+	if !type_fits(key_type, &prop_type) {
+		let message = format!(
+			"`{}` (type `{}`) cannot be provided as `{}` (type `{}`)",
+			provide.path.join("."),
+			prop_type.name().cyan(),
+			provide.key,
+			key_type.name().cyan(),
+		);
+		Issue::error(message, provide.span.clone()).with_code("type-mismatch").report();
+		return Err(());
+	}
+	Ok(())
+}
+
+/// checks that every context key injected by `child` (or any of its `else` branches) is provided by its parent
+fn check_child_injects(
+	scope: &Module,
+	parent: &ComponentDef,
+	provided: &[String],
+	child: &ParserElement,
+) -> Result<(), ()> {
+	let mut next = Some(child);
+	while let Some(element) = next {
+		let def = scope.get_component_def(element)?;
+		for key in def.injects.iter() {
+			if !parent.provides.contains(key) && !provided.contains(key) {
+				let message = format!("`{}` injects `{}`, which `{}` does not provide", def.name, key, parent.name);
+				Issue::error(message, element.name_span.clone()).with_code("missing-context").report();
+				return Err(());
+			}
+		}
+		next = element.else_branch.as_deref();
+	}
+	Ok(())
 }
 
 pub fn check_component(scope: &mut Module, unchecked: &ParserComponent) -> Result<Component, ()> {
+	scope.declare_enums(&unchecked.enums);
+	// every prop type is resolved before bailing out, later checks would only repeat the same issues
+	let mut unchecked_props = Vec::new();
+	let mut failed = false;
+	for prop in unchecked.props.values() {
+		let Ok(prop_type) = scope.resolve_type(&prop.prop_type, &prop.span) else {
+			failed = true;
+			continue;
+		};
+		scope.set_prop_type(&prop.name, prop_type.clone());
+		unchecked_props.push(PropDecl { prop_type, ..prop.clone() });
+	}
+	if failed {
+		return Err(());
+	}
+
+	let mut props = Vec::new();
+	let mut computed = HashMap::new();
+	let mut injects = Vec::new();
+	for prop in unchecked_props.iter() {
+		if let Some(key) = &prop.inject {
+			if check_inject(scope, prop, key).is_err() {
+				failed = true;
+			}
+			if !injects.contains(key) {
+				injects.push(key.clone());
+			}
+		}
+		let Ok((checked_prop, checked)) = check_prop_decl(scope, prop) else {
+			failed = true;
+			continue;
+		};
+		if let (true, Some(_)) = (prop.is_readonly, checked) {
+			let mut fields = Vec::new();
+			if check_fields(scope, &prop.name, prop.default.as_ref().unwrap(), &prop.prop_type, &mut fields).is_err() {
+				failed = true;
+			}
+			computed.extend(fields);
+		}
+		props.push(checked_prop);
+	}
+	if !failed && check_computed_cycles(&props, &computed).is_err() {
+		failed = true;
+	}
+
+	let root_def = scope.get_component_def(&unchecked.root)?;
+	let mut provided = Vec::new();
+	for provide in unchecked.provides.iter() {
+		if check_provide(scope, provide).is_err() {
+			failed = true;
+			continue;
+		}
+		if !root_def.container {
+			let message = format!("`{}` cannot provide context values because it cannot contain children", root_def.name);
+			Issue::error(message, provide.span.clone()).with_code("invalid-provide").report();
+			failed = true;
+			continue;
+		}
+		provided.push(provide.key.clone());
+	}
+	// the root element receives whatever is injected into the component
+	for key in root_def.injects.iter() {
+		if !injects.contains(key) {
+			injects.push(key.clone());
+		}
+	}
+
+	scope.slots = unchecked.slots.clone();
+	if check_slot_placements(&unchecked.root, false, &mut Vec::new()).is_err() {
+		failed = true;
+	}
+	let root = check_element(scope, &unchecked.root, &provided)?;
+	if failed {
+		return Err(());
+	}
+	let mut children = None;
+	find_children_placeholder(&root, false, &mut children)?;
+	// outer children are injected with whatever their new container provides
+	let mut child_provides = Vec::new();
+	if let Some((c, container)) = &children {
+		let def = scope.get_component_def_at(&container.tag.path, &c.span)?;
+		child_provides.extend(def.provides.iter().cloned());
+		if std::ptr::eq(*container, &root) {
+			child_provides.extend(unchecked.provides.iter().map(|e| e.key.clone()));
+		}
+	}
+	let children = children.map(|(c, _)| c);
+
+	let mut imports: Vec<_> = scope.imports
+		.iter()
+		.map(|(alias, path)| {
+			let component = &scope.components[path];
+			let enums = component.enums.iter().map(|e| e.name.clone()).collect();
+			(alias.clone(), component.name.clone(), enums)
+		})
+		.collect();
+	imports.sort();
+
+	let mut used = Vec::new();
+	collect_bindings(&root, &mut used);
+	for expr in computed.values() {
+		used.extend(expr.bindings.iter().cloned());
+	}
+	used.extend(unchecked.provides.iter().map(|e| e.path.join(".")));
+	// the reads could be in content that was skipped after a syntax error
+	for prop in props.iter().filter(|e| !e.is_pub && !unchecked.syntax_errors) {
+		let nested = format!("{}.", prop.name);
+		if !used.iter().any(|e| *e == prop.name || e.starts_with(&nested)) {
+			let message = format!("private property `{}` is never read", prop.name);
+			Issue::warning(message, prop.span.clone()).with_code("unused").report();
+		}
+	}
+
 	let checked = Component {
 		name: unchecked.name.clone(),
-		root: check_element(scope, &unchecked.root)?,
-		props: unchecked.props.values().cloned().collect(),
+		root,
+		props,
+		computed,
+		provides: unchecked.provides.clone(),
+		injects,
+		enums: unchecked.enums.clone(),
+		children,
+		child_provides,
+		slots: unchecked.slots.clone(),
+		events: root_def.events.clone(),
+		imports,
 	};
 
 	return Ok(checked);
 }
 
+/// Finds the (single) children placeholder of a component. Placeholders can't be repeated, since
+/// the outer children can only be appended to one container.
+fn find_children_placeholder<'a>(
+	element: &'a Element,
+	repeated: bool,
+	found: &mut Option<(Children, &'a Element)>,
+) -> Result<(), ()> {
+	let repeated = repeated || element.repeater.is_some();
+	let elements = std::iter::once(element).chain(element.alternatives.iter().map(|e| &e.element));
+	for element in elements {
+		for slot in element.slots.values() {
+			find_children_placeholder(slot, repeated, found)?;
+		}
+		for child in element.children.iter() {
+			match child {
+				Content::Children(c) => {
+					if found.is_some() {
+						let message = "children placeholder declared more than once";
+						Issue::error(message, c.span.clone()).with_code("duplicate-placeholder").report();
+						return Err(());
+					}
+					if repeated {
+						let message = "children placeholders cannot be used inside a `for ... in ...` element";
+						Issue::error(message, c.span.clone()).with_code("invalid-placeholder").report();
+						return Err(());
+					}
+					*found = Some((c.clone(), element));
+				},
+				Content::Element(e) => find_children_placeholder(e, repeated, found)?,
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Collects the bindings read anywhere in the tree of `element`.
+fn collect_bindings(element: &Element, out: &mut Vec<String>) {
+	let exprs = element.props.values()
+		.chain(element.presets.values())
+		.chain(element.events.values())
+		.chain(element.condition.iter())
+		.chain(element.repeater.iter().map(|e| &e.collection));
+	for expr in exprs {
+		out.extend(expr.bindings.iter().cloned());
+	}
+	for alternative in element.alternatives.iter() {
+		collect_bindings(&alternative.element, out);
+	}
+	for slot in element.slots.values() {
+		collect_bindings(slot, out);
+	}
+	for child in element.children.iter() {
+		if let Content::Element(e) = child {
+			collect_bindings(e, out);
+		}
+	}
+}
+
+/// Each slot's content is a single component instance, so it can only be placed once.
+fn check_slot_placements(element: &ParserElement, repeated: bool, placed: &mut Vec<String>) -> Result<(), ()> {
+	let repeated = repeated || element.repeater.is_some();
+	for child in element.children.iter() {
+		match child {
+			ParserContent::Slot(name, span) => {
+				if placed.contains(name) {
+					let message = format!("slot `{name}` placed more than once");
+					Issue::error(message, span.clone()).with_code("duplicate-placeholder").report();
+					return Err(());
+				}
+				if repeated {
+					let message = "slots cannot be placed inside a `for ... in ...` element";
+					Issue::error(message, span.clone()).with_code("invalid-placeholder").report();
+					return Err(());
+				}
+				placed.push(name.clone());
+			},
+			ParserContent::Element(e) => check_slot_placements(e, repeated, placed)?,
+			_ => {},
+		}
+	}
+	if let Some(else_branch) = element.else_branch.as_ref() {
+		check_slot_placements(else_branch, repeated, placed)?;
+	}
+	for slot in element.slots.values() {
+		check_slot_placements(&slot.element, repeated, placed)?;
+	}
+	Ok(())
+}
+
 fn try_coerce(value: CheckedExpr, t: &Type) -> CheckedExpr {
 	if value.expr_type == *t {
 		return value;
@@ -107,6 +490,17 @@ fn try_coerce(value: CheckedExpr, t: &Type) -> CheckedExpr {
 			expr_type: Type::Float,
 			bindings,
 		},
+		(Type::Float, CheckedExpr { expr, expr_type: Type::Int, bindings }) => {
+			let span = expr.span.clone();
+			CheckedExpr {
+				expr: Expr {
+					value: ExprValue::Coerce(Box::new(expr), Type::Float),
+					span,
+				},
+				expr_type: Type::Float,
+				bindings,
+			}
+		}
 		(Type::String, CheckedExpr { expr, bindings, .. }) => {
 			let span = expr.span.clone();
 			CheckedExpr {
@@ -122,6 +516,461 @@ fn try_coerce(value: CheckedExpr, t: &Type) -> CheckedExpr {
 	}
 }
 
+fn merge_bindings(bindings: &mut Vec<String>, other: &[String]) {
+	for binding in other {
+		if !bindings.contains(binding) {
+			bindings.push(binding.clone());
+		}
+	}
+}
+
+fn arithmetic_type(op: BinaryOp, lhs: &Type, rhs: &Type) -> Option<Type> {
+	match (op, lhs, rhs) {
+		(_, Type::Int, Type::Int) => Some(Type::Int),
+		(_, Type::Float, Type::Float) => Some(Type::Float),
+		(BinaryOp::Add | BinaryOp::Sub, Type::Length, Type::Length) => Some(Type::Length),
+		(BinaryOp::Mul | BinaryOp::Div, Type::Length, Type::Int | Type::Float) => Some(Type::Length),
+		(BinaryOp::Mul, Type::Int | Type::Float, Type::Length) => Some(Type::Length),
+		_ => None,
+	}
+}
+
+fn binary_type(op: BinaryOp, lhs: &Type, rhs: &Type) -> Option<Type> {
+	match op {
+		BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => arithmetic_type(op, lhs, rhs),
+		BinaryOp::Eq | BinaryOp::NotEq => match (lhs, rhs) {
+			(
+				Type::Int | Type::Float | Type::Length | Type::Brush | Type::String | Type::Boolean | Type::Enum(..),
+				_,
+			) if lhs == rhs => Some(Type::Boolean),
+			_ => None,
+		},
+		BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq => match (lhs, rhs) {
+			// lengths can't be ordered before layout, e.g. `a + b` is only known once both are resolved
+			(Type::Int | Type::Float | Type::String, _) if lhs == rhs => Some(Type::Boolean),
+			_ => None,
+		},
+		BinaryOp::And | BinaryOp::Or => match (lhs, rhs) {
+			(Type::Boolean, Type::Boolean) => Some(Type::Boolean),
+			_ => None,
+		},
+	}
+}
+
+fn check_binary(
+	scope: &Module,
+	lhs: &Expr,
+	op: BinaryOp,
+	rhs: &Expr,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let mut lhs = check_expr(scope, lhs, None)?;
+	// an enum on the right-hand side takes its type from the left-hand side, e.g. `layout == .row`
+	let mut rhs = if let ExprValue::Enum(..) = rhs.value {
+		check_expr(scope, rhs, Some(&lhs.expr_type))?
+	} else {
+		check_expr(scope, rhs, None)?
+	};
+
+	// an Int operand is promoted when the other operand is a Float
+	match (&lhs.expr_type, &rhs.expr_type) {
+		(Type::Int, Type::Float) => lhs = try_coerce(lhs, &Type::Float),
+		(Type::Float, Type::Int) => rhs = try_coerce(rhs, &Type::Float),
+		_ => {},
+	}
+
+	let expr_type = if let Some(t) = binary_type(op, &lhs.expr_type, &rhs.expr_type) {
+		t
+	} else {
+		let message = format!(
+			"operator `{}` cannot be applied to types `{}` and `{}`",
+			op.symbol(),
+			lhs.expr_type.name().cyan(),
+			rhs.expr_type.name().cyan(),
+		);
+		Issue::error(message, span).with_code("type-mismatch").report();
+		return Err(());
+	};
+
+	let mut bindings = lhs.bindings;
+	merge_bindings(&mut bindings, &rhs.bindings);
+	let operand_types = (lhs.expr_type, rhs.expr_type);
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Binary(Box::new(lhs.expr), op, Box::new(rhs.expr), Some(operand_types)),
+			span,
+		},
+		expr_type,
+		bindings,
+	})
+}
+
+fn check_unary(scope: &Module, op: UnaryOp, operand: &Expr, span: Span) -> Result<CheckedExpr, ()> {
+	let operand = check_expr(scope, operand, None)?;
+	let expr_type = match (op, &operand.expr_type) {
+		(UnaryOp::Not, Type::Boolean) => Type::Boolean,
+		(UnaryOp::Neg, t @ (Type::Int | Type::Float | Type::Length)) => t.clone(),
+		_ => {
+			let message = format!(
+				"operator `{}` cannot be applied to type `{}`",
+				op.symbol(),
+				operand.expr_type.name().cyan(),
+			);
+			Issue::error(message, span).with_code("type-mismatch").report();
+			return Err(());
+		}
+	};
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Unary(op, Box::new(operand.expr)),
+			span,
+		},
+		expr_type,
+		bindings: operand.bindings,
+	})
+}
+
+fn check_conditional(
+	scope: &Module,
+	condition: &Expr,
+	then_expr: &Expr,
+	else_expr: &Expr,
+	implicit_type: Option<&Type>,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let condition = type_check(scope, condition, &Type::Boolean)?;
+	let mut then_expr = check_expr(scope, then_expr, implicit_type)?;
+	let mut else_expr = check_expr(scope, else_expr, implicit_type)?;
+
+	match (&then_expr.expr_type, &else_expr.expr_type) {
+		(Type::Int, Type::Float) => then_expr = try_coerce(then_expr, &Type::Float),
+		(Type::Float, Type::Int) => else_expr = try_coerce(else_expr, &Type::Float),
+		_ => {},
+	}
+
+	if then_expr.expr_type != else_expr.expr_type {
+		let message = format!(
+			"conditional branches have incompatible types `{}` and `{}`",
+			then_expr.expr_type.name().cyan(),
+			else_expr.expr_type.name().cyan(),
+		);
+		Issue::error(message, span).with_code("type-mismatch").report();
+		return Err(());
+	}
+
+	let mut bindings = condition.bindings;
+	merge_bindings(&mut bindings, &then_expr.bindings);
+	merge_bindings(&mut bindings, &else_expr.bindings);
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Conditional(
+				Box::new(condition.expr),
+				Box::new(then_expr.expr),
+				Box::new(else_expr.expr),
+			),
+			span,
+		},
+		expr_type: then_expr.expr_type,
+		bindings,
+	})
+}
+
+fn check_match(
+	scope: &Module,
+	subject: &Expr,
+	arms: &[MatchArm],
+	implicit_type: Option<&Type>,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let subject = check_expr(scope, subject, None)?;
+	let mut bindings = subject.bindings.clone();
+	let mut seen: Vec<String> = Vec::new();
+	let mut has_wildcard = false;
+	let mut checked_arms = Vec::new();
+
+	for arm in arms {
+		if has_wildcard {
+			let message = "unreachable match arm";
+			Issue::error(message, arm.span.clone()).with_code("unreachable-arm").report();
+			return Err(());
+		}
+		let pattern = match &arm.pattern {
+			Some(pattern) => {
+				let checked = type_check(scope, pattern, &subject.expr_type)?;
+				if !checked.bindings.is_empty() {
+					let message = "match patterns must be constant";
+					Issue::error(message, pattern.span.clone()).with_code("invalid-pattern").report();
+					return Err(());
+				}
+				let key = match &checked.expr.value {
+					ExprValue::Enum(name, _) => Some(format!(".{name}")),
+					ExprValue::Boolean(value) => Some(format!("{value}")),
+					ExprValue::Int(value) => Some(format!("{value}")),
+					ExprValue::String(value) => Some(format!("{value:?}")),
+					_ => None,
+				};
+				if let Some(key) = key {
+					if seen.contains(&key) {
+						let message = format!("`{key}` is matched more than once");
+						Issue::error(message, pattern.span.clone()).with_code("unreachable-arm").report();
+						return Err(());
+					}
+					seen.push(key);
+				}
+				Some(checked.expr)
+			},
+			None => {
+				has_wildcard = true;
+				None
+			},
+		};
+		let value = check_expr(scope, &arm.value, implicit_type)?;
+		merge_bindings(&mut bindings, &value.bindings);
+		checked_arms.push((pattern, value, arm.span.clone()));
+	}
+
+	if !has_wildcard {
+		let missing: Vec<String> = match &subject.expr_type {
+			Type::Enum(def) => def.variants.iter().map(|v| format!(".{v}")).collect(),
+			Type::Boolean => vec!["true".to_owned(), "false".to_owned()],
+			_ => {
+				let message = format!(
+					"non-exhaustive match: values of type `{}` require a `_` arm",
+					subject.expr_type.name().cyan(),
+				);
+				Issue::error(message, span).with_code("non-exhaustive-match").report();
+				return Err(());
+			},
+		};
+		let missing: Vec<String> = missing.into_iter().filter(|m| !seen.contains(m)).map(|m| format!("`{m}`")).collect();
+		if !missing.is_empty() {
+			let message = format!("non-exhaustive match: missing {}", missing.join(", "));
+			Issue::error(message, span).with_code("non-exhaustive-match").report();
+			return Err(());
+		}
+	}
+
+	if checked_arms.iter().any(|(_, value, _)| value.expr_type == Type::Float) {
+		for (_, value, _) in checked_arms.iter_mut() {
+			if value.expr_type == Type::Int {
+				*value = try_coerce(value.clone(), &Type::Float);
+			}
+		}
+	}
+
+	let expr_type = checked_arms[0].1.expr_type.clone();
+	for (_, value, arm_span) in &checked_arms {
+		if value.expr_type != expr_type {
+			let message = format!(
+				"match arms have incompatible types `{}` and `{}`",
+				expr_type.name().cyan(),
+				value.expr_type.name().cyan(),
+			);
+			Issue::error(message, arm_span.clone()).with_code("type-mismatch").report();
+			return Err(());
+		}
+	}
+
+	let arms = checked_arms
+		.into_iter()
+		.map(|(pattern, value, span)| MatchArm { pattern, value: value.expr, span })
+		.collect();
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Match(Box::new(subject.expr), arms),
+			span,
+		},
+		expr_type,
+		bindings,
+	})
+}
+
+fn check_object(
+	scope: &Module,
+	fields: HashMap<String, Expr>,
+	implicit_type: Option<&Type>,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let expected = match implicit_type {
+		Some(Type::Object(expected)) => Some(expected),
+		_ => None,
+	};
+
+	if let Some(expected) = expected {
+		let mut missing: Vec<_> = expected.keys().filter(|e| !fields.contains_key(*e)).map(|e| format!("`{e}`")).collect();
+		if !missing.is_empty() {
+			missing.sort();
+			let message = format!(
+				"missing {} {} in object of type `{}`",
+				if missing.len() == 1 { "field" } else { "fields" },
+				missing.join(", "),
+				implicit_type.unwrap().name().cyan(),
+			);
+			Issue::error(message, span).with_code("missing-field").report();
+			return Err(());
+		}
+	}
+
+	let mut checked_fields = HashMap::new();
+	let mut field_types = HashMap::new();
+	let mut bindings = Vec::new();
+	for (name, value) in fields {
+		let checked = if let Some(expected) = expected {
+			let field = expected.get(&name).ok_or_else(|| {
+				let message = format!("no field `{}` in type `{}`", name, implicit_type.unwrap().name().cyan());
+				Issue::error(message, value.span.clone()).with_code("unknown-field").report();
+			})?;
+			type_check(scope, &value, &field.prop_type)?
+		} else {
+			check_expr(scope, &value, None)?
+		};
+		merge_bindings(&mut bindings, &checked.bindings);
+		let field = PropDecl::field(name.clone(), checked.expr_type, checked.expr.span.clone());
+		field_types.insert(name.clone(), field);
+		checked_fields.insert(name, checked.expr);
+	}
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Object(checked_fields),
+			span,
+		},
+		expr_type: Type::Object(field_types),
+		bindings,
+	})
+}
+
+fn check_list(
+	scope: &Module,
+	items: Vec<Expr>,
+	implicit_type: Option<&Type>,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let mut item_type = match implicit_type {
+		Some(Type::List(item_type)) => Some(*item_type.clone()),
+		_ => None,
+	};
+
+	let mut checked_items = Vec::new();
+	let mut bindings = Vec::new();
+	for item in items.iter() {
+		let checked = if let Some(item_type) = &item_type {
+			type_check(scope, item, item_type)?
+		} else {
+			check_expr(scope, item, None)?
+		};
+		if let Type::Object(..) = checked.expr_type {
+			let message = "lists of objects are not supported";
+			Issue::error(message, checked.expr.span.clone()).with_code("unsupported").report();
+			return Err(());
+		}
+		item_type.get_or_insert_with(|| checked.expr_type.clone());
+		merge_bindings(&mut bindings, &checked.bindings);
+		checked_items.push(checked.expr);
+	}
+
+	let item_type = item_type.ok_or_else(|| {
+		let message = "cannot infer the item type of an empty list";
+		Issue::error(message, span.clone()).with_code("type-inference").report();
+	})?;
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::List(checked_items, Some(item_type.clone())),
+			span,
+		},
+		expr_type: Type::List(Box::new(item_type)),
+		bindings,
+	})
+}
+
+fn check_index(scope: &Module, list: &Expr, index: &Expr, span: Span) -> Result<CheckedExpr, ()> {
+	let list = check_expr(scope, list, None)?;
+	let item_type = if let Type::List(item_type) = &list.expr_type {
+		*item_type.clone()
+	} else {
+		let message = format!("type `{}` cannot be indexed", list.expr_type.name().cyan());
+		Issue::error(message, list.expr.span.clone()).with_code("type-mismatch").report();
+		return Err(());
+	};
+	let index = type_check(scope, index, &Type::Int)?;
+
+	let mut bindings = list.bindings;
+	merge_bindings(&mut bindings, &index.bindings);
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Index(Box::new(list.expr), Box::new(index.expr)),
+			span,
+		},
+		expr_type: item_type,
+		bindings,
+	})
+}
+
+fn check_range(
+	scope: &Module,
+	start: &Expr,
+	end: &Expr,
+	inclusive: bool,
+	step: Option<&Expr>,
+	span: Span,
+) -> Result<CheckedExpr, ()> {
+	let start = type_check(scope, start, &Type::Int)?;
+	let end = type_check(scope, end, &Type::Int)?;
+	let step = step.map(|e| type_check(scope, e, &Type::Int)).transpose()?;
+
+	let mut bindings = start.bindings;
+	merge_bindings(&mut bindings, &end.bindings);
+	if let Some(step) = &step {
+		if let ExprValue::Int(0) = step.expr.value {
+			let message = "range step cannot be zero";
+			Issue::error(message, step.expr.span.clone()).with_code("invalid-range").report();
+			return Err(());
+		}
+		merge_bindings(&mut bindings, &step.bindings);
+	}
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Range(
+				Box::new(start.expr),
+				Box::new(end.expr),
+				inclusive,
+				step.map(|e| Box::new(e.expr)),
+			),
+			span,
+		},
+		expr_type: Type::Range,
+		bindings,
+	})
+}
+
+fn check_member(scope: &Module, object: &Expr, name: &str, span: Span) -> Result<CheckedExpr, ()> {
+	let object = check_expr(scope, object, None)?;
+	let member_type = match (&object.expr_type, name) {
+		(Type::List(..), "len") => Type::Int,
+		_ => {
+			let message = format!("type `{}` has no member `{}`", object.expr_type.name().cyan(), name);
+			Issue::error(message, span).with_code("unknown-member").report();
+			return Err(());
+		}
+	};
+
+	Ok(CheckedExpr {
+		expr: Expr {
+			value: ExprValue::Member(Box::new(object.expr), name.to_owned()),
+			span,
+		},
+		expr_type: member_type,
+		bindings: object.bindings,
+	})
+}
+
 fn check_expr(
 	scope: &Module,
 	expr: &Expr,
@@ -131,6 +980,13 @@ fn check_expr(
 	let checked = match expr.value {
 		ExprValue::Px(..) => CheckedExpr::primitive(expr, Type::Length),
 		ExprValue::Float(..) => CheckedExpr::primitive(expr, Type::Float),
+		ExprValue::Int(n) if !(-MAX_INT..=MAX_INT).contains(&n) => {
+			Issue::error("integer literal out of range", expr.span.clone())
+				.with_code("out-of-range")
+				.with_help(format!("`Int` values must lie within -{MAX_INT}..={MAX_INT}"))
+				.report();
+			return Err(());
+		},
 		ExprValue::Int(..) => CheckedExpr::primitive(expr, Type::Int),
 		ExprValue::Color(..) => CheckedExpr::primitive(expr, Type::Brush),
 		ExprValue::String(..) => CheckedExpr::primitive(expr, Type::String),
@@ -140,43 +996,46 @@ fn check_expr(
 				Some(m) => m,
 				None => {
 					let message = format!("enum expression is illegal in this context");
-					eprintln!("{}", Issue::error(message, expr.span.clone()));
+					Issue::error(message, expr.span.clone()).with_code("invalid-enum").report();
 					return Err(());
 				}
 			};
-			let (enum_name, enum_values) = match implicit_type {
-				Type::EnumLayout => (
-					"Layout".to_owned(),
-					hashset!("row".to_owned(), "column".to_owned()),
-				),
+			let def = match implicit_type {
+				Type::Enum(def) => def,
 				_ => {
 					let message = format!(
 						"expected type `{}`, found unknown enum type",
 						implicit_type.name().cyan(),
 					);
-					eprintln!("{}", Issue::error(message, expr.span.clone()));
+					Issue::error(message, expr.span.clone()).with_code("invalid-enum").report();
 					return Err(());
 				}
 			};
-			if !enum_values.contains(&name) {
+			if !def.variants.contains(&name) {
 				let message = format!(
 					"`{}` is not a valid member of enum type `{}`",
 					name,
-					enum_name.cyan(),
+					def.name.cyan(),
 				);
-				eprintln!("{}", Issue::error(message, expr.span.clone()));
+				Issue::error(message, expr.span.clone()).with_code("unknown-variant").report();
 				return Err(());
 			}
 			CheckedExpr {
 				expr: Expr {
-					value: ExprValue::Enum(name, Some(enum_name)),
+					value: ExprValue::Enum(name, Some(def.clone())),
 					span: expr.span,
 				},
 				expr_type: implicit_type.clone(),
 				bindings: Vec::new(),
 			}
 		},
-		ExprValue::Object(..) => unimplemented!(),
+		ExprValue::Object(fields) => check_object(scope, fields, implicit_type, expr.span.clone())?,
+		ExprValue::List(items, _) => check_list(scope, items, implicit_type, expr.span.clone())?,
+		ExprValue::Index(ref list, ref index) => check_index(scope, list, index, expr.span.clone())?,
+		ExprValue::Member(ref object, ref name) => check_member(scope, object, name, expr.span.clone())?,
+		ExprValue::Range(ref start, ref end, inclusive, ref step) => {
+			check_range(scope, start, end, inclusive, step.as_deref(), expr.span.clone())?
+		}
 		ExprValue::Coerce(..) => unimplemented!(),
 		ExprValue::FunctionCall(ref fn_expr, ref received_args) => {
 			let checked_fn_expr = check_expr(scope, &fn_expr, implicit_type)?;
@@ -189,13 +1048,13 @@ fn check_expr(
 							expected_args.len(),
 							received_args.len(),
 						);
-						eprintln!("{}", Issue::error(message, expr.span.clone()));
+						Issue::error(message, expr.span.clone()).with_code("argument-count").report();
 						return Err(());
 					}
 					let mut checked_args = Vec::new();
 					for (arg, t) in received_args.iter().zip(expected_args) {
 						let checked_arg = type_check(scope, arg, &t)?;
-						bindings.append(&mut checked_arg.bindings.clone());
+						merge_bindings(&mut bindings, &checked_arg.bindings);
 						checked_args.push(checked_arg.expr);
 					}
 					CheckedExpr {
@@ -215,40 +1074,41 @@ fn check_expr(
 						"expression of type `{}` is not callable",
 						checked_fn_expr.expr_type.name().cyan()
 					);
-					eprintln!(
-						"{}",
-						Issue::error(message, checked_fn_expr.expr.span.clone())
-					);
+					Issue::error(message, checked_fn_expr.expr.span.clone()).with_code("type-mismatch").report();
 					return Err(());
 				}
 			}
 		}
-		ExprValue::Path(path, ctx) => {
-			assert!(ctx == Ctx::Component);
-			if let Some((checked_ctx, expr_type)) = scope.lookup(&path, &expr.span)? {
-				let bindings = if checked_ctx == Ctx::Component {
-					path.clone()
-				} else {
-					Vec::new()
-				};
-				CheckedExpr {
-					expr: Expr {
-						value: ExprValue::Path(path, checked_ctx),
-						span: expr.span,
-					},
-					expr_type,
-					bindings,
-				}
-			} else {
-				eprintln!(
-					"{}",
-					Issue::error(
-						format!("property not found: `{}`", path[0]),
-						expr.span.clone()
-					)
-				);
+		ExprValue::Binary(ref lhs, op, ref rhs, _) => {
+			check_binary(scope, lhs, op, rhs, expr.span.clone())?
+		}
+		ExprValue::Unary(op, ref operand) => {
+			check_unary(scope, op, operand, expr.span.clone())?
+		}
+		ExprValue::Conditional(ref condition, ref then_expr, ref else_expr) => {
+			check_conditional(scope, condition, then_expr, else_expr, implicit_type, expr.span.clone())?
+		}
+		ExprValue::Match(ref subject, ref arms) => {
+			if arms.is_empty() {
+				let message = "match expressions must have at least one arm";
+				Issue::error(message, expr.span.clone()).with_code("non-exhaustive-match").report();
 				return Err(());
 			}
+			check_match(scope, subject, arms, implicit_type, expr.span.clone())?
+		}
+		ExprValue::Path(ref path, _) if path.len() > 1 && path.last().unwrap() == "len" => {
+			// `items.len` reads the length of a list rather than a property called `len`
+			let list_path = path[..path.len() - 1].to_vec();
+			if let Some((_, Type::List(..))) = scope.lookup(&list_path, &expr.span)? {
+				let list = Expr { value: ExprValue::Path(list_path, Ctx::Component), span: expr.span.clone() };
+				check_member(scope, &list, "len", expr.span.clone())?
+			} else {
+				check_path(scope, path.clone(), expr.span.clone())?
+			}
+		}
+		ExprValue::Path(path, ctx) => {
+			assert!(ctx == Ctx::Component);
+			check_path(scope, path, expr.span.clone())?
 		}
 	};
 	if let Some(t) = implicit_type {
@@ -258,15 +1118,60 @@ fn check_expr(
 	}
 }
 
+fn check_path(scope: &Module, path: Vec<String>, span: Span) -> Result<CheckedExpr, ()> {
+	// fully qualified enum values, e.g. `Enum.Layout.row`
+	if path.len() > 1 {
+		let (variant, enum_path) = path.split_last().unwrap();
+		if let Some((_, Type::EnumDecl(def))) = scope.lookup(enum_path, &span)? {
+			if !def.variants.contains(variant) {
+				let message = format!(
+					"`{}` is not a valid member of enum type `{}`",
+					variant,
+					def.name.cyan(),
+				);
+				Issue::error(message, span).with_code("unknown-variant").report();
+				return Err(());
+			}
+			return Ok(CheckedExpr {
+				expr: Expr {
+					value: ExprValue::Enum(variant.clone(), Some(def.clone())),
+					span,
+				},
+				expr_type: Type::Enum(def),
+				bindings: Vec::new(),
+			});
+		}
+	}
+
+	if let Some((checked_ctx, expr_type)) = scope.lookup(&path, &span)? {
+		let bindings = if checked_ctx == Ctx::Component {
+			vec![path.join(".")]
+		} else {
+			Vec::new()
+		};
+		Ok(CheckedExpr {
+			expr: Expr {
+				value: ExprValue::Path(path, checked_ctx),
+				span,
+			},
+			expr_type,
+			bindings,
+		})
+	} else {
+		Issue::error(format!("property not found: `{}`", path[0]), span).with_code("unknown-property").report();
+		Err(())
+	}
+}
+
 fn type_check(scope: &Module, expr: &Expr, t: &Type) -> Result<CheckedExpr, ()> {
 	let expr = check_expr(scope, expr, Some(t))?;
-	if expr.expr_type != *t {
+	if !type_fits(t, &expr.expr_type) {
 		let message = format!(
 			"expected type `{}`, found `{}`",
 			t.name().cyan(),
 			expr.expr_type.name().cyan()
 		);
-		eprintln!("{}", Issue::error(message, expr.expr.span.clone()));
+		Issue::error(message, expr.expr.span.clone()).with_code("type-mismatch").report();
 		Err(())
 	} else {
 		Ok(expr)
@@ -284,7 +1189,12 @@ fn check_child_rules(
 		ChildRules::Any => Ok(rules),
 		ChildRules::AnyOf(v) => {
 			// TODO: this is hacky and broken. Don't use name to identify type. Same goes for Component & Function Types
-			if !v.contains(child.tag.path.last().unwrap()) {
+			let mut name = child.tag.path.last().unwrap().clone();
+			// slots are identified by the root type their component is constrained to
+			if let Some(Type::Element(Some(root_type))) = child.props.get("component").map(|e| &e.expr_type) {
+				name = root_type.name();
+			}
+			if !v.contains(&name) {
 				let permitted = v
 					.iter()
 					.map(|e| format!("`{e}`"))
@@ -294,7 +1204,7 @@ fn check_child_rules(
 					"invalid child element for `{}` (permitted elements: {permitted})",
 					def.name
 				);
-				eprintln!("{}", Issue::error(message, child_span.clone()));
+				Issue::error(message, child_span.clone()).with_code("invalid-child").report();
 				return Err(());
 			}
 			Ok(rules)
@@ -303,16 +1213,133 @@ fn check_child_rules(
 		ChildRules::ExactCount(_) => unimplemented!(),
 		ChildRules::None => {
 			let message = format!("`{}` component cannot contain children", def.name);
-			eprintln!("{}", Issue::error(message, parent_span.clone()));
+			Issue::error(message, parent_span.clone()).with_code("invalid-child").report();
 			return Err(());
 		}
 	}
 }
 
-pub fn check_element(scope: &mut Module, unchecked: &ParserElement) -> Result<Element, ()> {
-	let mut children = Vec::new();
+fn check_children_placeholder(scope: &Module, def: &ComponentDef, children: &Children) -> Result<(), ()> {
+	let placeholder = if children.single { "@child" } else { "@children" };
+	if let ChildRules::None = def.child_rules {
+		let message = format!("`{}` component cannot contain children", def.name);
+		Issue::error(message, children.span.clone()).with_code("invalid-child").report();
+		return Err(());
+	}
+	for name in children.filter.iter().flatten() {
+		match scope.lookup(std::slice::from_ref(name), &children.span)? {
+			Some((_, Type::Component(..))) => {},
+			_ => {
+				let message = format!("`{name}` is not a component");
+				Issue::error(message, children.span.clone()).with_code("unknown-component").report();
+				return Err(());
+			},
+		}
+	}
+	if let ChildRules::AnyOf(permitted) = &def.child_rules {
+		let filter = children.filter.as_ref().unwrap_or(&Vec::new()).clone();
+		if filter.is_empty() || filter.iter().any(|e| !permitted.contains(e)) {
+			let permitted = permitted
+				.iter()
+				.map(|e| format!("`{e}`"))
+				.collect::<Vec<_>>()
+				.join(", ");
+			let message = format!(
+				"`{placeholder}` must be filtered to the permitted child elements of `{}` ({permitted})",
+				def.name,
+			);
+			Issue::error(message, children.span.clone()).with_code("invalid-child").report();
+			return Err(());
+		}
+	}
+	Ok(())
+}
+
+/// Type checks `expr` against `t`, splitting object-typed values into one entry per scalar field
+/// (keyed by the dotted path under `name`), since those are bound field by field.
+fn check_fields(scope: &Module, name: &str, expr: &Expr, t: &Type, out: &mut Vec<(String, CheckedExpr)>) -> Result<(), ()> {
+	let checked = type_check(scope, expr, t)?;
+	let fields = if let Type::Object(fields) = t {
+		fields
+	} else {
+		out.push((name.to_owned(), checked));
+		return Ok(());
+	};
 
+	let mut fields: Vec<_> = fields.values().collect();
+	fields.sort_by(|a, b| a.name.cmp(&b.name));
+	for field in fields {
+		let projected = expr.field(&field.name).ok_or_else(|| {
+			let message = format!("`{name}` (type `{}`) cannot be assigned field by field from this expression", t.name().cyan());
+			Issue::error(message, expr.span.clone()).with_code("type-mismatch").report();
+		})?;
+		check_fields(scope, &format!("{name}.{}", field.name), &projected, &field.prop_type, out)?;
+	}
+	Ok(())
+}
+
+/// Resolves a (possibly nested) property assignment like `padding.top` against `def`, returning
+/// the definition of the top-level property along with the type of the assigned field.
+fn lookup_prop_path<'a>(def: &'a ComponentDef, path: &str, span: &Span) -> Result<(&'a PropDef, Type), ()> {
+	let mut segments = path.split('.');
+	let first = segments.next().unwrap();
+	let prop_def = def.props.get(first).ok_or_else(|| {
+		let message = format!("{first}: no such property");
+		Issue::error(message, span.clone()).with_code("unknown-property").report();
+	})?;
+
+	let mut prop_type = &prop_def.prop_type;
+	let mut parent = first.to_owned();
+	for segment in segments {
+		let fields = if let Type::Object(fields) = prop_type {
+			fields
+		} else {
+			let message = format!("`{}` (type `{}`) has no child properties", parent, prop_type.name().cyan());
+			Issue::error(message, span.clone()).with_code("unknown-property").report();
+			return Err(());
+		};
+		let field = fields.get(segment).ok_or_else(|| {
+			let message = format!("`{}` (type `{}`) has no property `{}`", parent, prop_type.name().cyan(), segment);
+			Issue::error(message, span.clone()).with_code("unknown-property").report();
+		})?;
+		prop_type = &field.prop_type;
+		parent = format!("{parent}.{segment}");
+	}
+
+	Ok((prop_def, prop_type.clone()))
+}
+
+pub fn check_element(scope: &mut Module, unchecked: &ParserElement, provided: &[String]) -> Result<Element, ()> {
 	scope.push_scope();
+	let checked = check_element_scoped(scope, unchecked, provided);
+	scope.pop_scope();
+
+	// `else` branches are flattened into a single ordered list on the first element of the chain,
+	// and are checked even if the element itself failed so that their issues are reported as well
+	let alternatives = if let Some(else_branch) = &unchecked.else_branch {
+		check_element(scope, else_branch, &[]).map(|mut element| {
+			let mut alternatives = std::mem::take(&mut element.alternatives);
+			alternatives.insert(0, Alternative {
+				condition: element.condition.take(),
+				element,
+			});
+			alternatives
+		})
+	} else {
+		Ok(Vec::new())
+	};
+
+	let mut checked = checked?;
+	checked.alternatives = alternatives?;
+	Ok(checked)
+}
+
+/// Checks everything but the `else` branches of an element, within the scope of its repeater
+/// bindings. Failing props and children are reported and skipped so their siblings still get
+/// checked.
+fn check_element_scoped(scope: &mut Module, unchecked: &ParserElement, provided: &[String]) -> Result<Element, ()> {
+	let mut children = Vec::new();
+	let mut failed = false;
 
 	assert!(unchecked.condition.is_none() || unchecked.repeater.is_none());
 
@@ -331,7 +1358,7 @@ pub fn check_element(scope: &mut Module, unchecked: &ParserElement) -> Result<El
 				"type `{}` is not iterable",
 				collection.expr_type.name().cyan()
 			);
-			eprintln!("{}", Issue::error(message, collection.expr.span.clone()));
+			Issue::error(message, collection.expr.span.clone()).with_code("type-mismatch").report();
 			return Err(());
 		};
 		if let Some((binding, span)) = &repeater.index {
@@ -346,7 +1373,6 @@ pub fn check_element(scope: &mut Module, unchecked: &ParserElement) -> Result<El
 			item: repeater.item.clone().map(|(s, _)| s),
 			collection,
 			item_type,
-			root_type: unchecked.path[0].clone(),
 		})
 	} else {
 		None
@@ -357,13 +1383,45 @@ pub fn check_element(scope: &mut Module, unchecked: &ParserElement) -> Result<El
 	let mut checked_presets = HashMap::new();
 	let mut clobbered = Vec::new();
 	let mut spans = HashMap::new();
+	let import = scope.get_import(&unchecked.path).map(|(_, component)| component);
 	for (k, p) in unchecked.props.iter() {
-		let prop_def = component_def.props.get(k).ok_or_else(|| {
-			let message = format!("{k}: no such property");
-			eprintln!("{}", Issue::error(message, p.span.clone()));
-		})?;
-		let expr = type_check(scope, &p.expr, &prop_def.prop_type)?;
-		if prop_def.children.len() > 0 {
+		let first = k.split('.').next().unwrap();
+		if let Some(prop) = import.and_then(|e| e.props.iter().find(|e| e.name == first && !e.is_pub)) {
+			let message = format!("`{}` is a private property of `{}`", prop.name, component_def.name);
+			Issue::error(message, p.span.clone())
+				.with_code("private-property")
+				.with_note("properties can only be assigned from outside their component if declared `pub`")
+				.report();
+			failed = true;
+			continue;
+		}
+		let Ok((prop_def, prop_type)) = lookup_prop_path(&component_def, k, &p.span) else {
+			failed = true;
+			continue;
+		};
+		if prop_def.readonly {
+			let message = format!("cannot assign to readonly property `{k}`");
+			Issue::error(message, p.span.clone()).with_code("readonly-property").report();
+			failed = true;
+			continue;
+		}
+		if let Type::Object(..) = prop_type {
+			let mut fields = Vec::new();
+			if check_fields(scope, k, &p.expr, &prop_type, &mut fields).is_err() {
+				failed = true;
+				continue;
+			}
+			for (k, expr) in fields {
+				spans.insert(k.clone(), p.span.clone());
+				checked_props.insert(k, expr);
+			}
+			continue;
+		}
+		let Ok(expr) = type_check(scope, &p.expr, &prop_type) else {
+			failed = true;
+			continue;
+		};
+		if prop_def.children.len() > 0 && !k.contains('.') {
 			spans.insert(k.clone(), p.span.clone());
 			checked_presets.insert(k.clone(), expr);
 			clobbered.extend(
@@ -380,45 +1438,455 @@ pub fn check_element(scope: &mut Module, unchecked: &ParserElement) -> Result<El
 	}
 
 	for (clobberer, clobbered) in clobbered {
-		if checked_props.contains_key(&clobbered) || checked_presets.contains_key(&clobbered) {
-			let message = format!("`{clobbered}` is overridden by property `{clobberer}`");
-			eprintln!(
-				"{}",
-				Issue::error(message, spans.get(&clobbered).unwrap().clone())
-			);
-			return Err(());
+		let nested = format!("{clobbered}.");
+		let assigned = checked_props.keys()
+			.chain(checked_presets.keys())
+			.find(|e| **e == clobbered || e.starts_with(&nested));
+		if let Some(assigned) = assigned {
+			let message = format!("`{assigned}` is overridden by property `{clobberer}`");
+			Issue::error(message, spans[assigned].clone())
+				.with_code("conflicting-assignment")
+				.with_label(&spans[&clobberer], format!("`{clobberer}` also sets `{clobbered}`"))
+				.with_help(format!("remove the assignment to `{assigned}` or to `{clobberer}`"))
+				.report();
+			failed = true;
+		}
+	}
+
+	let mut checked_events = HashMap::new();
+	for (name, event) in unchecked.events.iter() {
+		if !component_def.events.contains(name) {
+			let message = if component_def.events.is_empty() {
+				format!("`{}` has no events", component_def.name)
+			} else {
+				let events = component_def.events.iter().map(|e| format!("`{e}`")).collect::<Vec<_>>().join(", ");
+				format!("`{}` has no event `{name}` (available events: {events})", component_def.name)
+			};
+			Issue::error(message, event.span.clone()).with_code("unknown-event").report();
+			failed = true;
+			continue;
+		}
+		let Ok(handler) = type_check(scope, &event.handler, &Type::Callback) else {
+			failed = true;
+			continue;
+		};
+		// handlers are looked up when the event fires, so they need to be component properties
+		if !matches!(handler.expr.value, ExprValue::Path(_, Ctx::Component)) {
+			let message = "event handlers must be `Callback` properties of the component";
+			Issue::error(message, event.handler.span.clone()).with_code("invalid-handler").report();
+			failed = true;
+			continue;
 		}
+		checked_events.insert(name.clone(), handler);
+	}
+
+	let mut checked_slots = HashMap::new();
+	for (name, slot) in unchecked.slots.iter() {
+		let import = scope.get_import(&unchecked.path).map(|(_, component)| component);
+		if import.is_some_and(|e| e.props.iter().any(|e| e.name == *name && !e.is_pub)) {
+			let message = format!("`{name}` is a private property of `{}`", component_def.name);
+			Issue::error(message, slot.span.clone())
+				.with_code("private-property")
+				.with_note("properties can only be assigned from outside their component if declared `pub`")
+				.report();
+			failed = true;
+			continue;
+		}
+		// declared slots are `Component` properties too, so both are filled the same way
+		let bound = match component_def.props.get(name) {
+			Some(PropDef { readonly: true, .. }) => {
+				let message = format!("cannot assign to readonly property `{name}`");
+				Issue::error(message, slot.span.clone()).with_code("readonly-property").report();
+				failed = true;
+				continue;
+			},
+			Some(PropDef { prop_type: Type::Element(bound), .. }) => bound,
+			Some(PropDef { prop_type, .. }) => {
+				let message = format!("`{name}` (type `{}`) cannot hold an element", prop_type.name().cyan());
+				Issue::error(message, slot.span.clone()).with_code("type-mismatch").report();
+				failed = true;
+				continue;
+			},
+			_ => {
+				let message = format!("`{}` has no slot or `Component` property named `{name}`", component_def.name);
+				Issue::error(message, slot.span.clone()).with_code("unknown-slot").report();
+				failed = true;
+				continue;
+			},
+		};
+		let Ok(element) = check_element(scope, &slot.element, &[]) else {
+			failed = true;
+			continue;
+		};
+		if let Some(bound) = bound.as_deref() {
+			if !fits_element_bound(scope, &element.tag, bound) {
+				let message = format!(
+					"`{name}` (type `{}`) cannot hold a `{}` element",
+					Type::Element(Some(Box::new(bound.clone()))).name().cyan(),
+					slot.element.path.join("."),
+				);
+				Issue::error(message, slot.element.name_span.clone()).with_code("type-mismatch").report();
+				failed = true;
+				continue;
+			}
+		}
+		checked_slots.insert(name.clone(), element);
 	}
 
 	let mut rules = component_def.child_rules.clone();
 	for child in unchecked.children.iter() {
-		match child {
+		let (e, span) = match child {
+			// the parser already reported whatever it had to skip here
+			ParserContent::Error(_) => continue,
 			ParserContent::Children(c) => {
+				if check_children_placeholder(scope, &component_def, c).is_err() {
+					failed = true;
+				}
 				children.push(Content::Children(c.clone()));
+				continue;
+			}
+			ParserContent::Slot(name, span) => {
+				if !scope.slots.contains(name) {
+					let message = format!("no slot named `{name}` is declared (declare it with `slot {name};`)");
+					Issue::error(message, span.clone()).with_code("unknown-slot").report();
+					failed = true;
+					continue;
+				}
+				let value = Expr { value: ExprValue::Path(vec![name.clone()], Ctx::Component), span: span.clone() };
+				(check_element(scope, &ParserElement::slot(value), &[]), span)
+			}
+			ParserContent::Value(value) => {
+				let e = match check_expr(scope, value, None).map(|e| e.expr_type) {
+					Ok(Type::Element(..)) => ParserElement::slot(value.clone()),
+					Ok(_) => ParserElement::text(value.clone()),
+					Err(()) => {
+						failed = true;
+						continue;
+					},
+				};
+				(check_element(scope, &e, &[]), &value.span)
 			}
 			ParserContent::Element(e) => {
-				let child_span = &e.name_span;
-				let e = check_element(scope, &e)?;
-				rules = check_child_rules(&e, &component_def, &unchecked.name_span, child_span, rules)?;
-				children.push(Content::Element(e));
+				// the child's definition was looked up (and any issue with it reported) here already
+				if check_child_injects(scope, &component_def, provided, e).is_err() {
+					failed = true;
+					continue;
+				}
+				(check_element(scope, &e, &[]), &e.name_span)
 			}
+		};
+		let Ok(e) = e else {
+			failed = true;
+			continue;
+		};
+		let elements = std::iter::once(&e).chain(e.alternatives.iter().map(|e| &e.element));
+		for element in elements {
+			match check_child_rules(element, &component_def, &unchecked.name_span, span, rules.clone()) {
+				Ok(next) => rules = next,
+				Err(()) => failed = true,
+			}
+		}
+		children.push(Content::Element(e));
+	}
+
+	if component_def.single_child {
+		let mut elements = children.iter().filter_map(|e| match e {
+			Content::Element(e) => Some(e),
+			_ => None,
+		});
+		if elements.clone().count() > 1 || elements.any(|e| e.repeater.is_some()) {
+			let message = format!("`{}` can only contain a single child element", component_def.name);
+			Issue::error(message, unchecked.name_span.clone()).with_code("invalid-child").report();
+			failed = true;
 		}
 	}
 
+	if failed {
+		return Err(());
+	}
+
 	let checked = Element {
 		tag: ElementTag {
 			path: unchecked.path.clone(),
-			import_path: None,
+			import_path: scope.get_import(&unchecked.path).map(|(path, _)| path.clone()),
 		},
 		data: None,
 		condition,
+		alternatives: Vec::new(),
 		repeater,
 		props: checked_props,
 		presets: checked_presets,
+		slots: checked_slots,
+		events: checked_events,
 		children: children,
 	};
 
-	scope.pop_scope();
-
 	return Ok(checked);
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{ IssueLevel, test_util::{check_files, codes} };
+
+	#[test]
+	fn negation_operand_types() {
+		let source = "pub n: Int = 1;\npub f: Float = 1.0;\npub l: Length = 1px;\n\
+			pub readonly a: Int = (-n);\npub readonly b: Float = (-f);\npub readonly c: Length = (-l);\nRect {}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "pub b: Boolean;\npub readonly c: Boolean = (-b);\nRect {}")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+	}
+
+	#[test]
+	fn int_literals_out_of_range() {
+		let output = check_files(&[("app.ui", "pub n: Int = 9007199254740992;\nRect {}")]);
+		assert_eq!(codes(&output), ["out-of-range"]);
+
+		let output = check_files(&[("app.ui", "pub n: Int = -9007199254740991;\nRect {}")]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+	}
+
+	#[test]
+	fn conditional_branch_types() {
+		let source = "pub flag: Boolean;\npub readonly a: Float = (flag ? 1 : 2.5);\nRect {}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let source = "pub flag: Boolean;\npub readonly a: Length = (flag ? 1px : \"a\");\nRect {}";
+		assert_eq!(codes(&check_files(&[("app.ui", source)])), ["type-mismatch"]);
+
+		let source = "pub n: Int;\npub readonly a: Int = (n ? 1 : 2);\nRect {}";
+		assert_eq!(codes(&check_files(&[("app.ui", source)])), ["type-mismatch"]);
+	}
+
+	#[test]
+	fn default_values() {
+		let source = "pub ratio: Float = 2;\npub size: Length = (20px * 2);\nRect {}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "pub size: Length = \"big\";\nRect {}")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let output = check_files(&[("app.ui", "pub a: Int = 1;\npub b: Int = (a + 1);\nRect {}")]);
+		assert_eq!(codes(&output), ["invalid-default"]);
+	}
+
+	#[test]
+	fn readonly_property_assignment() {
+		let output = check_files(&[
+			("app.ui", "import \"./card\";\nRect {\n\tCard { area: 2; }\n}"),
+			("card.ui", "pub readonly area: Int = 1;\nRect {}"),
+		]);
+		assert_eq!(codes(&output), ["readonly-property"]);
+		assert_eq!(output.diagnostics[0].message, "cannot assign to readonly property `area`");
+	}
+
+	#[test]
+	fn inject_and_provide_types() {
+		let output = check_files(&[("app.ui", "inject(FrameSize) size: Int;\nRect {}")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let output = check_files(&[("app.ui", "pub n: Int = 1;\nprovide(FrameSize) n;\nRect {}")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let source = "inject(FrameSize) size: { width: Length; height: Length; };\nprovide(FrameSize) size;\nRect {}";
+		assert!(check_files(&[("app.ui", source)]).diagnostics.is_empty());
+	}
+
+	#[test]
+	fn enum_values() {
+		let source = "enum Align { start, center }\npub a: Align = .start;\npub readonly c: Boolean = (a == .center);\nRect {}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "enum Align { start, center }\npub a: Align = .middle;\nRect {}")]);
+		assert_eq!(codes(&output), ["unknown-variant"]);
+	}
+
+	#[test]
+	fn qualified_enum_paths() {
+		let source = "enum Align { start, end }\npub layout: Enum.Layout = .column;\npub flag: Boolean;\n\
+			pub readonly row: Boolean = (Enum.Layout.row == layout);\npub readonly align: Align = (flag ? Align.start : Align.end);\nRect {}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "pub readonly a: Boolean = (Enum.Layout.diagonal == .row);\nRect {}")]);
+		assert_eq!(codes(&output), ["unknown-variant"]);
+
+		let output = check_files(&[("app.ui", "enum Align { start }\npub readonly a: Align = (Align.end);\nRect {}")]);
+		assert_eq!(codes(&output), ["unknown-variant"]);
+	}
+
+	#[test]
+	fn non_exhaustive_match() {
+		let enum_match = "enum State { idle, active, done }\npub s: State = .idle;\n\
+			Rect { Text { content: (match (s) { .idle => \"i\", .active => \"a\" }); } }";
+		let output = check_files(&[("app.ui", enum_match)]);
+		assert_eq!(codes(&output), ["non-exhaustive-match"]);
+		assert_eq!(output.diagnostics[0].message, "non-exhaustive match: missing `.done`");
+
+		let int_match = "pub n: Int = 0;\nRect { Text { content: (match (n) { 0 => \"zero\" }); } }";
+		assert_eq!(codes(&check_files(&[("app.ui", int_match)])), ["non-exhaustive-match"]);
+
+		let repeated_arm = "pub b: Boolean;\nRect { Text { content: (match (b) { true => \"y\", true => \"n\", _ => \"-\" }); } }";
+		assert_eq!(codes(&check_files(&[("app.ui", repeated_arm)])), ["unreachable-arm"]);
+	}
+
+	#[test]
+	fn repeated_literal_match_arms() {
+		let source = "pub n: Int;\nRect { Text { content: (match (n) { 1 => \"a\", 2 => \"b\", 1 => \"c\", _ => \"d\" }); } }";
+		let output = check_files(&[("app.ui", source)]);
+		assert_eq!(codes(&output), ["unreachable-arm"]);
+		assert_eq!(output.diagnostics[0].message, "`1` is matched more than once");
+		assert_eq!(output.diagnostics[0].primary.start_column, 57);
+
+		let source = "pub s: String;\nRect { Text { content: (match (s) { \"a\" => 1, \"b\" => 2, \"a\" => 3, _ => 4 }); } }";
+		let output = check_files(&[("app.ui", source)]);
+		assert_eq!(codes(&output), ["unreachable-arm"]);
+		assert_eq!(output.diagnostics[0].message, "`\"a\"` is matched more than once");
+
+		// the same text as a string and as a number are different values
+		let source = "pub s: String;\nRect { Text { content: (match (s) { \"1\" => 1, \"true\" => 2, _ => 4 }); } }";
+		assert!(check_files(&[("app.ui", source)]).diagnostics.is_empty());
+	}
+
+	#[test]
+	fn component_properties() {
+		let card = ("card.ui", "pub thing: Component;\npub box: Component<Rect>;\npub title: String;\ninner: Component;\n\
+			Rect {\n\t(thing)\n\t(box)\n\t(inner)\n}");
+		let boxed = ("boxed.ui", "Rect {}");
+		let app = |content: &str| format!("import \"./card\";\nimport \"./boxed\";\nRect {{\n\tCard {{ {content} }}\n}}");
+
+		let output = check_files(&[("app.ui", &app("thing: { Text {} } box: { Rect {} }")), card, boxed]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+		// an imported component is bound by the element at its root
+		let output = check_files(&[("app.ui", &app("box: { Boxed {} }")), card, boxed]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", &app("box: { Text {} }")), card, boxed]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+		assert_eq!(output.diagnostics[0].primary.start_line, 4);
+
+		let output = check_files(&[("app.ui", &app("title: { Text {} }")), card, boxed]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let output = check_files(&[("app.ui", &app("inner: { Text {} }")), card, boxed]);
+		assert_eq!(codes(&output), ["private-property"]);
+
+		let output = check_files(&[("app.ui", &app("nope: { Text {} }")), card, boxed]);
+		assert_eq!(codes(&output), ["unknown-slot"]);
+	}
+
+	#[test]
+	fn single_child_placeholder() {
+		let one = ("one.ui", "Rect { Rect { @child } }");
+		let output = check_files(&[("app.ui", "import \"./one\";\nRect { One { Rect {} Rect {} } }"), one]);
+		assert_eq!(codes(&output), ["invalid-child"]);
+		assert_eq!(output.diagnostics[0].message, "`One` can only contain a single child element");
+
+		let output = check_files(&[("app.ui", "import \"./one\";\nRect { One { Rect {} } }"), one]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+	}
+
+	#[test]
+	fn imported_components() {
+		let list = ("list.ui", "pub title: String;\nRect {\n\t@children\n}");
+		let output = check_files(&[("app.ui", "import \"./list\" as Items;\nRect { Items { title: \"a\"; Rect {} } }"), list]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "import \"./list\" as Items;\nRect { List {} }"), list]);
+		assert_eq!(codes(&output), ["unknown-component"]);
+
+		let output = check_files(&[("app.ui", "import \"./missing\";\nRect {}")]);
+		assert_eq!(codes(&output), ["import"]);
+
+		let output = check_files(&[("a.ui", "import \"./b\";\nRect { B {} }"), ("b.ui", "import \"./a\";\nRect { A {} }")]);
+		assert_eq!(codes(&output), ["import"]);
+		assert!(output.diagnostics[0].message.starts_with("encountered recursive import"));
+	}
+
+	#[test]
+	fn private_property_assignment() {
+		let output = check_files(&[
+			("app.ui", "import \"./card\";\nRect {\n\tCard { secret: 2; }\n}"),
+			("card.ui", "secret: Int = 1;\nRect { Text { content: (secret); } }"),
+		]);
+		assert_eq!(codes(&output), ["private-property"]);
+		assert_eq!(output.diagnostics[0].message, "`secret` is a private property of `Card`");
+		assert_eq!(output.diagnostics[0].notes.len(), 1);
+		assert_eq!(output.diagnostics[0].primary.start_line, 3);
+	}
+
+	#[test]
+	fn reports_issues_from_every_stage() {
+		let source = "pub x: Length = 3px;\nRect {\n\tx1: (x + ;\n\ty1: (x * x);\n\tText { content: (zz); }\n\tBogus {}\n}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(!output.success);
+		assert_eq!(codes(&output), ["parse", "type-mismatch", "unknown-property", "unknown-component"]);
+	}
+
+	#[test]
+	fn duplicates_point_at_the_first_occurrence() {
+		let output = check_files(&[("app.ui", "pub x: Int = 1;\npub x: Int = 2;\nRect {}")]);
+		let diagnostic = &output.diagnostics[0];
+		assert_eq!(diagnostic.message, "property `x` declared more than once");
+		assert_eq!(diagnostic.primary.start_line, 2);
+		assert_eq!(diagnostic.secondary[0].message, "first declared here");
+		assert_eq!(diagnostic.secondary[0].span.start_line, 1);
+
+		let output = check_files(&[("app.ui", "pub x: Int = 1;\nRect {\n\tPane for x in (3) {}\n}")]);
+		assert_eq!(codes(&output), ["shadowed-binding"]);
+		assert_eq!(output.diagnostics[0].level, IssueLevel::Warning);
+		assert_eq!(output.diagnostics[0].secondary[0].message, "previously declared here");
+		assert_eq!(output.diagnostics[0].secondary[0].span.start_line, 1);
+	}
+
+	#[test]
+	fn nested_assignment_paths() {
+		let card = ("card.ui", "struct Pad { top: Length; left: Length; }\npub pad: Pad = { top: 0px, left: 0px };\nRect {}");
+		let output = check_files(&[("app.ui", "import \"./card\";\nRect { Card { pad.top: 1px; pad.left: 2px; } }"), card]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "import \"./card\";\nRect { Card { pad.right: 1px; } }"), card]);
+		assert_eq!(codes(&output), ["unknown-property"]);
+
+		let output = check_files(&[("app.ui", "import \"./card\";\nRect { Card { pad: { left: \"a\" }; } }"), card]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+	}
+
+	#[test]
+	fn object_literals_and_fields() {
+		let point = "struct Point { x: Length; y: Length; }\n";
+		let output = check_files(&[("app.ui", &format!("{point}pub p: Point = {{ x: 1px, y: 2px }};\nRect {{ x1: (p.x); }}"))]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", &format!("{point}pub p: Point = {{ x: 1px }};\nRect {{}}"))]);
+		assert_eq!(codes(&output), ["missing-field"]);
+
+		let output = check_files(&[("app.ui", &format!("{point}pub p: Point = {{ x: 1px, y: 2px, z: 3px }};\nRect {{}}"))]);
+		assert_eq!(codes(&output), ["unknown-field"]);
+
+		let output = check_files(&[("app.ui", &format!("{point}pub p: Point = {{ x: 1px, y: 2px }};\nRect {{ x1: (p.z); }}"))]);
+		assert_eq!(codes(&output), ["unknown-property"]);
+	}
+
+	#[test]
+	fn lists() {
+		let source = "pub xs: [Int] = [1, 2];\npub empty: [String] = [];\n\
+			Rect {\n\tText { content: (xs[0] + xs.len); }\n\tText for i, x in (xs) { content: (x * i); }\n}";
+		let output = check_files(&[("app.ui", source)]);
+		assert!(output.diagnostics.is_empty(), "{:#?}", output.diagnostics);
+
+		let output = check_files(&[("app.ui", "pub xs: [Int] = [1, \"a\"];\nRect {}")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let output = check_files(&[("app.ui", "pub xs: [Int] = [1];\nRect { Text { content: (xs[\"a\"]); } }")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+
+		let output = check_files(&[("app.ui", "pub n: Int = 1;\nRect { Text { content: (n[0]); } }")]);
+		assert_eq!(codes(&output), ["type-mismatch"]);
+	}
+}
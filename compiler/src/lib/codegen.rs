@@ -1,13 +1,14 @@
-use std::{path::PathBuf, fmt};
+use std::{path::PathBuf, fmt, cmp::Ordering};
 use std::fs::File;
 use std::io::Write as IoWrite;
 
-use colored::*;
 use convert_case::{Casing, Case};
 
-use crate::{Type, checker::Content, ExprValue, Ctx, chk::CheckedExpr};
+use crate::{PropDecl, Type, EnumDef, checker::Content, Expr, ExprValue, BinaryOp, UnaryOp, Ctx, MAX_INT, chk::CheckedExpr};
 
-use super::checker::{Component, Element};
+use super::checker::{Component, Element, ElementTag};
+use crate::issue::Issue;
+use crate::source_file::Span;
 
 fn type_to_js(prop_type: &Type) -> String {
 	match prop_type {
@@ -17,10 +18,41 @@ fn type_to_js(prop_type: &Type) -> String {
 		Type::Brush => "Dom.Brush".to_owned(),
 		Type::String => "Dom.String".to_owned(),
 		Type::Boolean => "Dom.Boolean".to_owned(),
+		Type::List(item_type) => format!("Dom.List.of({})", type_to_js(item_type)),
+		Type::Range => "Dom.Range".to_owned(),
+		Type::Enum(def) => enum_to_js(def),
+		Type::Element(..) => "Dom.Component".to_owned(),
+		Type::Callback => "Dom.Callback".to_owned(),
 		_ => unimplemented!(),
 	}
 }
 
+fn enum_to_js(def: &EnumDef) -> String {
+	if def.builtin {
+		format!("Dom.Enum.{}", def.name)
+	} else {
+		def.name.clone()
+	}
+}
+
+/// user enums are emitted as classes of singleton values, mirroring the runtime's builtin enums
+fn generate_enum(def: &EnumDef) -> String {
+	let name = &def.name;
+	let variants: Vec<_> = def.variants.iter().map(|e| e.to_case(Case::UpperCamel)).collect();
+	let values: String = variants.iter().map(|e| format!("static {e} = new {name}();\n\t")).collect();
+	let first = &variants[0];
+	format!(
+		"export class {name} implements Dom.Property {{
+	{values}static default() {{ return this.{first}; }}
+	static coerce(e: any) {{ return e instanceof this ? e : this.{first}; }}
+	private constructor() {{}}
+	interpolate(next: this, _fac: number) {{ return next; }}
+	equals(other: this): boolean {{ return other === this; }}
+}}
+"
+	)
+}
+
 #[derive(Debug, Clone)]
 enum StaticValue {
 	Px(f64),
@@ -29,6 +61,7 @@ enum StaticValue {
 	Color(f64, f64, f64, f64),
 	String(String),
 	Boolean(bool),
+	Object(Vec<(String, StaticValue)>),
 	BuiltinFunction(BuiltinFunction),
 }
 
@@ -50,10 +83,42 @@ impl ExprValue {
 			ExprValue::Color(r,g,b,a) => Some(StaticValue::Color(*r,*g,*b,*a)),
 			ExprValue::String(value) => Some(StaticValue::String(value.clone())),
 			ExprValue::Boolean(value) => Some(StaticValue::Boolean(*value)),
-			ExprValue::Object(..) => unimplemented!(),
+			ExprValue::Object(fields) => {
+				let mut fields = fields
+					.iter()
+					.map(|(k, v)| Some((k.clone(), v.value.try_into_static()?)))
+					.collect::<Option<Vec<_>>>()?;
+				fields.sort_by(|a, b| a.0.cmp(&b.0));
+				Some(StaticValue::Object(fields))
+			},
+			ExprValue::List(..) => None,
+			ExprValue::Index(..) => None,
+			ExprValue::Member(..) => None,
+			ExprValue::Range(..) => None,
 			ExprValue::Path(..) => None,
 			ExprValue::Coerce(..) => None,
 			ExprValue::Enum(..) => None,
+			ExprValue::Match(..) => None,
+			ExprValue::Conditional(condition, then_expr, else_expr) => match condition.value.try_into_static()? {
+				StaticValue::Boolean(true) => then_expr.value.try_into_static(),
+				StaticValue::Boolean(false) => else_expr.value.try_into_static(),
+				_ => None,
+			},
+			ExprValue::Unary(UnaryOp::Not, expr) => match expr.value.try_into_static()? {
+				StaticValue::Boolean(value) => Some(StaticValue::Boolean(!value)),
+				_ => None,
+			},
+			ExprValue::Unary(UnaryOp::Neg, expr) => match expr.value.try_into_static()? {
+				StaticValue::Int(n) => n.checked_neg().map(StaticValue::Int),
+				StaticValue::Float(n) => Some(StaticValue::Float(-n)),
+				StaticValue::Px(n) => Some(StaticValue::Px(-n)),
+				_ => None,
+			},
+			ExprValue::Binary(lhs, op, rhs, _) => {
+				let lhs = lhs.value.try_into_static()?;
+				let rhs = rhs.value.try_into_static()?;
+				lhs.binary(*op, &rhs)
+			},
 			ExprValue::FunctionCall(expr, args) => {
 				let expr = expr.value.try_into_static();
 				let args: Vec<_> = args.iter().map(|e| e.value.try_into_static()).collect();
@@ -71,6 +136,88 @@ impl ExprValue {
 	}
 }
 
+impl StaticValue {
+	fn binary(&self, op: BinaryOp, rhs: &StaticValue) -> Option<StaticValue> {
+		match op {
+			BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div => self.arithmetic(op, rhs),
+			BinaryOp::And | BinaryOp::Or => match (self, rhs) {
+				(StaticValue::Boolean(a), StaticValue::Boolean(b)) => {
+					Some(StaticValue::Boolean(if op == BinaryOp::And { *a && *b } else { *a || *b }))
+				},
+				_ => None,
+			},
+			_ => {
+				let ordering = self.compare(rhs)?;
+				let result = match op {
+					BinaryOp::Eq => ordering == Ordering::Equal,
+					BinaryOp::NotEq => ordering != Ordering::Equal,
+					BinaryOp::Lt => ordering == Ordering::Less,
+					BinaryOp::LtEq => ordering != Ordering::Greater,
+					BinaryOp::Gt => ordering == Ordering::Greater,
+					BinaryOp::GtEq => ordering != Ordering::Less,
+					_ => unreachable!(),
+				};
+				Some(StaticValue::Boolean(result))
+			},
+		}
+	}
+
+	fn compare(&self, rhs: &StaticValue) -> Option<Ordering> {
+		match (self, rhs) {
+			(StaticValue::Px(a), StaticValue::Px(b)) => a.partial_cmp(b),
+			(StaticValue::Float(a), StaticValue::Float(b)) => a.partial_cmp(b),
+			(StaticValue::Int(a), StaticValue::Int(b)) => Some(a.cmp(b)),
+			(StaticValue::String(a), StaticValue::String(b)) => Some(a.cmp(b)),
+			(StaticValue::Boolean(a), StaticValue::Boolean(b)) => Some(a.cmp(b)),
+			_ => None,
+		}
+	}
+
+	fn arithmetic(&self, op: BinaryOp, rhs: &StaticValue) -> Option<StaticValue> {
+		fn arithmetic(op: BinaryOp, a: f64, b: f64) -> f64 {
+			match op {
+				BinaryOp::Add => a + b,
+				BinaryOp::Sub => a - b,
+				BinaryOp::Mul => a * b,
+				BinaryOp::Div => a / b,
+				_ => unreachable!(),
+			}
+		}
+
+		let result = match (self, op, rhs) {
+			(StaticValue::Px(a), BinaryOp::Add | BinaryOp::Sub, StaticValue::Px(b)) => {
+				Some(StaticValue::Px(arithmetic(op, *a, *b)))
+			},
+			(StaticValue::Px(a), BinaryOp::Mul | BinaryOp::Div, StaticValue::Int(b)) => {
+				Some(StaticValue::Px(arithmetic(op, *a, *b as f64)))
+			},
+			(StaticValue::Px(a), BinaryOp::Mul | BinaryOp::Div, StaticValue::Float(b)) => {
+				Some(StaticValue::Px(arithmetic(op, *a, *b)))
+			},
+			(StaticValue::Int(a), BinaryOp::Mul, StaticValue::Px(b)) => Some(StaticValue::Px(*a as f64 * b)),
+			(StaticValue::Float(a), BinaryOp::Mul, StaticValue::Px(b)) => Some(StaticValue::Px(a * b)),
+			// overflowing (beyond what the runtime represents exactly) or dividing by zero is left to the runtime
+			(StaticValue::Int(a), _, StaticValue::Int(b)) => {
+				let result = match op {
+					BinaryOp::Add => a.checked_add(*b),
+					BinaryOp::Sub => a.checked_sub(*b),
+					BinaryOp::Mul => a.checked_mul(*b),
+					BinaryOp::Div => a.checked_div(*b),
+					_ => unreachable!(),
+				};
+				result.filter(|n| (-MAX_INT..=MAX_INT).contains(n)).map(StaticValue::Int)
+			},
+			(StaticValue::Float(a), _, StaticValue::Float(b)) => Some(StaticValue::Float(arithmetic(op, *a, *b))),
+			_ => None,
+		};
+		// `inf` and `NaN` have no literal form in the generated code
+		match result {
+			Some(StaticValue::Px(n) | StaticValue::Float(n)) if !n.is_finite() => None,
+			result => result,
+		}
+	}
+}
+
 fn static_value_to_js(value: &StaticValue) -> String {
 	match value {
 		StaticValue::Px(n) => format!("Dom.Length.px({n})"),
@@ -82,6 +229,10 @@ fn static_value_to_js(value: &StaticValue) -> String {
 			format!("Dom.String.from({n})")
 		},
 		StaticValue::Boolean(n) => format!("Dom.Boolean.{}", if *n { "true" } else { "false" }),
+		StaticValue::Object(fields) => {
+			let fields: String = fields.iter().map(|(k, v)| format!("{k}: {}, ", static_value_to_js(v))).collect();
+			format!("{{ {fields}}}")
+		},
 		StaticValue::BuiltinFunction(..) => unreachable!(),
 	}
 }
@@ -103,50 +254,244 @@ fn generate_property_assignment(name: &str, expr: &CheckedExpr, is_preset: bool)
 		}
 	} else {
 		let rendered_expr = expr_to_js(&expr.expr.value);
-		let required_bindings: String = expr.bindings.iter().map(|e| format!("this.bindings.{e},")).collect();
-		let received_props = expr.bindings.join(",");
-		let received_props = if received_props == "" { "()".into() } else { format!("([{received_props}])") };
 		let binding = if is_preset {
 			format!("e.{name}")
 		} else {
 			format!("e.bindings.{name}")
 		};
-		format!("{binding}.connect([{required_bindings}], {received_props} => {rendered_expr}); ")
+		format!("{}; ", generate_connect(&binding, &expr.bindings, &rendered_expr))
+	}
+}
+
+fn generate_connect(binding: &str, bindings: &[String], rendered_expr: &str) -> String {
+	let required_bindings: String = bindings.iter().map(|e| format!("this.#bindings.{e},")).collect();
+	let received_props = bindings.iter().map(|e| e.replace('.', "$")).collect::<Vec<_>>().join(",");
+	let received_props = if received_props == "" { "()".into() } else { format!("([{received_props}])") };
+	format!("{binding}.connect([{required_bindings}], {received_props} => {rendered_expr})")
+}
+
+fn binding_to_js(prop_type: &Type, default: Option<&Expr>) -> String {
+	if let Type::Object(fields) = prop_type {
+		let mut fields: Vec<_> = fields.values().collect();
+		fields.sort_by(|a, b| a.name.cmp(&b.name));
+		let fields: String = fields
+			.iter()
+			.map(|e| {
+				let default = default.and_then(|default| default.field(&e.name));
+				format!("{}: {},", e.name, binding_to_js(&e.prop_type, default.as_ref()))
+			})
+			.collect();
+		format!("{{{fields}}}")
+	} else if let Some(default) = default {
+		format!("new Dom.Binding({}).set({})", type_to_js(prop_type), expr_to_js(&default.value))
+	} else {
+		format!("new Dom.Binding({})", type_to_js(prop_type))
+	}
+}
+
+fn generate_inject(target: &str, source: &str, prop_type: &Type) -> String {
+	if let Type::Object(fields) = prop_type {
+		let mut fields: Vec<_> = fields.values().collect();
+		fields.sort_by(|a, b| a.name.cmp(&b.name));
+		fields.iter()
+			.map(|e| generate_inject(&format!("{target}.{}", e.name), &format!("{source}.{}", e.name), &e.prop_type))
+			.collect()
+	} else {
+		format!("{target}.connect([{source}]); ")
+	}
+}
+
+fn scalar_to_js(expr: &Expr, scalar_type: &Type) -> String {
+	match (&expr.value, scalar_type) {
+		(ExprValue::Int(n), _) => format!("{n}"),
+		(ExprValue::Float(n), _) => format!("{n}"),
+		(ExprValue::Boolean(b), _) => format!("{b}"),
+		(ExprValue::Coerce(expr, _), _) => format!("Dom.Float.coerce({}).value", expr_to_js(&expr.value)),
+		// builtin functions return plain JS numbers rather than `Dom.Float` values
+		(ExprValue::FunctionCall(..), Type::Float) => format!("Dom.Float.coerce({}).value", expr_to_js(&expr.value)),
+		_ => format!("{}.value", expr_to_js(&expr.value)),
+	}
+}
+
+fn binary_to_js(lhs: &Expr, op: BinaryOp, rhs: &Expr, lhs_type: &Type, rhs_type: &Type) -> String {
+	let method = match op {
+		BinaryOp::Add => "add",
+		BinaryOp::Sub => "sub",
+		BinaryOp::Mul => "mul",
+		BinaryOp::Div => "div",
+		BinaryOp::Eq | BinaryOp::NotEq => {
+			let negate = if op == BinaryOp::NotEq { "!" } else { "" };
+			return format!("Dom.Boolean.from({negate}{}.equals({}))", expr_to_js(&lhs.value), expr_to_js(&rhs.value));
+		},
+		_ => {
+			return format!(
+				"Dom.Boolean.from({} {} {})",
+				scalar_to_js(lhs, lhs_type),
+				op.symbol(),
+				scalar_to_js(rhs, rhs_type),
+			);
+		},
+	};
+	match (lhs_type, rhs_type) {
+		(Type::Length, Type::Length) => {
+			format!("{}.{method}({})", expr_to_js(&lhs.value), expr_to_js(&rhs.value))
+		},
+		(Type::Length, _) => {
+			format!("{}.{method}({})", expr_to_js(&lhs.value), scalar_to_js(rhs, rhs_type))
+		},
+		(_, Type::Length) => {
+			format!("{}.{method}({})", expr_to_js(&rhs.value), scalar_to_js(lhs, lhs_type))
+		},
+		// integer division truncates, the same as when it is folded at compile time
+		(Type::Int, Type::Int) if op == BinaryOp::Div => {
+			format!("Dom.Int.from(Math.trunc({} / {}))", scalar_to_js(lhs, lhs_type), scalar_to_js(rhs, rhs_type))
+		},
+		_ => {
+			format!(
+				"{}.from({} {} {})",
+				type_to_js(lhs_type),
+				scalar_to_js(lhs, lhs_type),
+				op.symbol(),
+				scalar_to_js(rhs, rhs_type),
+			)
+		},
 	}
 }
 
 fn expr_to_js(value: &ExprValue) -> String {
+	if let Some(value) = value.try_into_static() {
+		return static_value_to_js(&value);
+	}
 	match value {
-		ExprValue::Enum(name, enum_name) => {
+		ExprValue::Enum(name, def) => {
 			let name = name.to_case(Case::UpperCamel);
-			format!("Dom.Enum.{}.{}", enum_name.clone().unwrap(), name)
+			format!("{}.{}", enum_to_js(def.as_ref().unwrap()), name)
 		}
 		ExprValue::Path(path, ctx) => {
-			let ctx = match ctx {
-				Ctx::Scope(_) => "",
-				Ctx::Component => "",
-				Ctx::Builtin => "Dom.Builtins.",
-			};
-			format!("{}{}", ctx, path.join("."))
+			match ctx {
+				Ctx::Scope(_) => path.join("."),
+				// matches the names given to received bindings in `generate_connect`
+				Ctx::Component => path.join("$"),
+				Ctx::Builtin => format!("Dom.Builtins.{}", path.join(".")),
+			}
 		},
 		ExprValue::Coerce(expr, coerce_type) => {
 			format!("{}.coerce({})", type_to_js(coerce_type), expr_to_js(&expr.value))
 		},
+		ExprValue::Binary(lhs, op, rhs, operand_types) => {
+			let (lhs_type, rhs_type) = operand_types.as_ref().unwrap();
+			binary_to_js(lhs, *op, rhs, lhs_type, rhs_type)
+		},
+		ExprValue::Conditional(condition, then_expr, else_expr) => {
+			format!(
+				"({} ? {} : {})",
+				scalar_to_js(condition, &Type::Boolean),
+				expr_to_js(&then_expr.value),
+				expr_to_js(&else_expr.value),
+			)
+		},
+		ExprValue::Match(subject, arms) => {
+			// the last arm is the fallback: it is either `_` or the only remaining case
+			let subject = expr_to_js(&subject.value);
+			let (last, rest) = arms.split_last().unwrap();
+			let mut js = expr_to_js(&last.value.value);
+			for arm in rest.iter().rev() {
+				let pattern = arm.pattern.as_ref().unwrap();
+				js = format!("{subject}.equals({}) ? {} : {js}", expr_to_js(&pattern.value), expr_to_js(&arm.value.value));
+			}
+			format!("({js})")
+		},
+		ExprValue::Unary(UnaryOp::Not, expr) => {
+			format!("Dom.Boolean.from(!{})", scalar_to_js(expr, &Type::Boolean))
+		},
+		ExprValue::Unary(UnaryOp::Neg, expr) => format!("{}.neg()", expr_to_js(&expr.value)),
 		ExprValue::FunctionCall(expr, args) => {
 			let args = args.iter().map(|e| expr_to_js(&e.value)).collect::<Vec<String>>().join(", ");
 			format!("{}({})", expr_to_js(&expr.value), args)
 		},
+		ExprValue::List(items, item_type) => {
+			let items: String = items.iter().map(|e| format!("{}, ", expr_to_js(&e.value))).collect();
+			format!("Dom.List.from({}, [{items}])", type_to_js(item_type.as_ref().unwrap()))
+		},
+		ExprValue::Index(list, index) => {
+			format!("{}.at({})", expr_to_js(&list.value), scalar_to_js(index, &Type::Int))
+		},
+		ExprValue::Member(object, name) => format!("{}.{name}", expr_to_js(&object.value)),
+		ExprValue::Range(start, end, inclusive, step) => {
+			format!(
+				"Dom.Range.from({}, {}, {inclusive}, {})",
+				scalar_to_js(start, &Type::Int),
+				scalar_to_js(end, &Type::Int),
+				step.as_ref().map_or("1".to_owned(), |e| scalar_to_js(e, &Type::Int)),
+			)
+		},
+		ExprValue::Object(fields) => {
+			let mut fields: Vec<_> = fields.iter().collect();
+			fields.sort_by(|a, b| a.0.cmp(b.0));
+			let fields: String = fields.iter().map(|(k, v)| format!("{k}: {}, ", expr_to_js(&v.value))).collect();
+			format!("{{ {fields}}}")
+		},
 		_ => unimplemented!("{:?}", value),
 	}
 }
 
+/// the TS class of an element: a builtin from the Dom module or an imported component
+fn element_class(tag: &ElementTag) -> String {
+	if tag.import_path.is_some() {
+		tag.path[0].clone()
+	} else {
+		format!("Dom.{}", tag.path[0])
+	}
+}
+
 fn generate_element(element: &Element) -> String {
-	generate_element_impl(element, false)
+	generate_element_impl(element, false, "")
+}
+
+fn generate_alternatives(element: &Element) -> String {
+	let mut branches = vec![generate_element_impl(element, true, "")];
+	let mut guards = vec![element.condition.as_ref()];
+	for alternative in element.alternatives.iter() {
+		branches.push(generate_element(&alternative.element));
+		guards.push(alternative.condition.as_ref());
+	}
+
+	let mut bindings = Vec::new();
+	for condition in guards.iter().flatten() {
+		for binding in condition.bindings.iter() {
+			if !bindings.contains(binding) {
+				bindings.push(binding.clone());
+			}
+		}
+	}
+
+	let mut rendered_expr = "e.emptyComponent".to_owned();
+	for (i, guard) in guards.into_iter().enumerate().rev() {
+		rendered_expr = if let Some(condition) = guard {
+			format!("{} ? branches[{i}] : {rendered_expr}", scalar_to_js(&condition.expr, &Type::Boolean))
+		} else {
+			format!("branches[{i}]")
+		};
+	}
+	let component = generate_connect("e.bindings.component", &bindings, &format!("({rendered_expr})"));
+
+	format!(
+		"(() => {{
+			let e = dom.Slot();
+			const branches = [{}];
+			e.props.insert = Dom.Boolean.true;
+			{component};
+			return e;
+		}})()",
+		branches.join(", "),
+	)
 }
 
-fn generate_element_impl(element: &Element, skip_if_for: bool) -> String {
+fn generate_element_impl(element: &Element, skip_if_for: bool, setup: &str) -> String {
 	if !skip_if_for {
-		if let Some(condition) = &element.condition {
+		if !element.alternatives.is_empty() {
+			return generate_alternatives(element);
+		} else if let Some(condition) = &element.condition {
 			let condition = generate_property_assignment("insert", &condition, false);
 			return format!(
 				"(() => {{
@@ -155,24 +500,25 @@ fn generate_element_impl(element: &Element, skip_if_for: bool) -> String {
 					{condition}
 					return e;
 				}})()",
-				generate_element_impl(element, true)
+				generate_element_impl(element, true, "")
 			);
 		} else if let Some(repeater) = &element.repeater {
 			let item_type = type_to_js(&repeater.item_type);
+			let collection_type = type_to_js(&repeater.collection.expr_type);
 			let index  = repeater.index.clone().unwrap_or("_$unused_index".into());
 			let item  = repeater.item.clone().unwrap_or("_$unused_item".into());
 			let collection = generate_property_assignment("collection", &repeater.collection, false);
 			return format!(
 				"(() => {{
-					let e = dom.Repeater<{item_type}, Dom.{}>
-					({item_type}, ({index}, {item}) => {{
+					let e = dom.Repeater<{item_type}, {}>
+					({collection_type}, ({index}, {item}) => {{
 						return [{}];
 					}});
 					{collection}
 					return e;
 				}})()",
-				repeater.root_type,
-				generate_element_impl(element, true)
+				element_class(&element.tag),
+				generate_element_impl(element, true, "")
 			);
 		}
 	}
@@ -181,57 +527,140 @@ fn generate_element_impl(element: &Element, skip_if_for: bool) -> String {
 		.map(|(k, v)| generate_property_assignment(k, v, false)).collect();
 	let presets: String = element.presets.iter()
 		.map(|(k, v)| generate_property_assignment(k, v, true)).collect();
+	let events: String = element.events.iter()
+		.map(|(k, v)| {
+			let handler = match &v.expr.value {
+				ExprValue::Path(path, _) => path.join("."),
+				_ => unreachable!(),
+			};
+			format!("e.events.{k}.addListener(() => this.#bindings.{handler}.get().call()); ")
+		})
+		.collect();
+	let slots: String = element.slots.iter()
+		.map(|(k, v)| format!("e.props.{k} = {}; ", generate_element(v))).collect();
 	let children: String = element.children.iter().map(|child| {
 		match child {
 			Content::Element(e) => {
 				format!("e.children.append({});", generate_element(e))
 			},
-			_ => unimplemented!(),
+			// children appended to the component are forwarded to this element
+			Content::Children(..) => "this.#children = e.children;".to_owned(),
 		}
 	}).collect();
+	let construct = if element.tag.import_path.is_some() {
+		format!("new {}(dom)", element.tag.path[0])
+	} else {
+		format!("dom.{}()", element.tag.path[0])
+	};
 	format!(
 		"(() => {{
-			let e = dom.{}();
-			{props}{presets}{children}
+			let e = {construct};
+			{setup}{props}{presets}{events}{slots}{children}
 			return e;
 		}})()",
-		element.tag.path[0])
+	)
 }
 
 pub fn generate<P: Into<PathBuf>>(
 	component: &Component,
 	path: P,
-) {
+) -> Option<PathBuf> {
 	let mut ctx = CodeGenCtx::new(&component.name, path);
 
 	let component_name = &component.name;
+	let model_prop = |prop: &PropDecl| {
+		let default = if prop.is_readonly || prop.inject.is_some() { None } else { prop.default.as_ref() };
+		format!("{}: {},", prop.name, binding_to_js(&prop.prop_type, default))
+	};
+	// only `pub` properties are exposed to the parent through `props` and `bindings`
 	let model_props: String = component.props
 		.iter()
-		.map(|prop| { format!("{}: new Dom.Binding({}),", prop.name, type_to_js(&prop.prop_type)) })
+		.filter(|prop| prop.is_pub)
+		.map(model_prop)
+		.collect();
+	let private_model_props: String = component.props
+		.iter()
+		.filter(|prop| !prop.is_pub)
+		.map(model_prop)
+		.collect();
+	let model = format!(
+		"#model = new Dom.Model({{{model_props}}}); #privateModel = new Dom.Model({{{private_model_props}}}); {}",
+		"#bindings = { ...this.#model.bindings, ...this.#privateModel.bindings };",
+	);
+	let injects: String = component.props
+		.iter()
+		.filter_map(|prop| prop.inject.as_ref().map(|key| (prop, key.to_case(Case::Camel))))
+		.map(|(prop, key)| {
+			let connects = generate_inject(&format!("this.#privateModel.bindings.{}", prop.name), &format!("deps.{key}"), &prop.prop_type);
+			format!("if(deps.{key}) {{ {connects}}} ")
+		})
 		.collect();
-	let model = format!("#model = new Dom.Model({{{model_props}}});");
-	let root_class = format!("{}", component.root.tag.path[0]);
-	let root = format!("readonly root: Dom.{root_class};");
-	let events = format!("readonly events: Dom.{root_class}['events'];");
-	let root_setup = format!("this.root = {}", generate_element(&component.root));
-	let constructor_body = format!("super(); {root_setup}; this.events = this.root.events; ");
+	let provides: String = component.provides
+		.iter()
+		.map(|provide| format!("{}: this.#bindings.{}, ", provide.key.to_case(Case::Camel), provide.path.join(".")))
+		.collect();
+	let provide_setup = if provides.is_empty() {
+		String::new()
+	} else {
+		format!("const provide = e.provide.bind(e); e.provide = () => ({{ ...provide(), {provides}}}); ")
+	};
+	let root_class = element_class(&component.root.tag);
+	let root = format!("readonly root: {root_class};");
+	let events = format!("readonly events: {root_class}['events'];");
+	let computed: String = component.computed
+		.iter()
+		.map(|(name, expr)| {
+			let binding = format!("this.#bindings.{name}");
+			if expr.bindings.is_empty() {
+				format!("{binding}.set({}).freeze(); ", expr_to_js(&expr.expr.value))
+			} else {
+				format!("{}.freeze(); ", generate_connect(&binding, &expr.bindings, &expr_to_js(&expr.expr.value)))
+			}
+		})
+		.collect();
+	let root_setup = format!("this.root = {}", generate_element_impl(&component.root, false, &provide_setup));
+	let constructor_body = format!("super(); {computed}{root_setup}; this.events = this.root.events; ");
 	let constructor = format!("constructor(dom: Dom.Dom) {{{constructor_body}}}");
-	let impls = format!("{} {} {} {} {}",
+	let impls = format!("{} {} {} {}",
 		"get props() { return this.#model.props; }",
 		"get bindings() { return this.#model.bindings; }",
 		"getRoots() { return [this.root]; }",
-		"get children() { return this.root.children }",
-		"inject(deps: { [key: string]: any }) { this.root.inject(deps); }",
+		if component.children.is_some() {
+			"get children() { return this.#children }"
+		} else {
+			"get children() { return this.root.children }"
+		},
 	);
-	let class_body = format!("{model} {root} {events} {constructor} {impls}");
+	let inject = format!("inject(deps: {{ [key: string]: any }}) {{ {injects}this.root.inject(deps); }}");
+	let children = if component.children.is_some() {
+		"#children!: Dom.Children<unknown>;"
+	} else {
+		""
+	};
+	let class_body = format!("{model} {children}{root} {events} {constructor} {impls} {inject}");
 
 	writeln!(ctx.file, "import * as Dom from '../dom';").unwrap();
+	// enums are identified by name, so one declared here or imported before is the same enum
+	let mut declared: Vec<&String> = component.enums.iter().map(|e| &e.name).collect();
+	for (alias, name, enums) in component.imports.iter() {
+		let enums: Vec<_> = enums.iter().filter(|e| !declared.contains(e)).collect();
+		let named = if enums.is_empty() {
+			String::new()
+		} else {
+			format!(", {{ {} }}", enums.iter().map(|e| e.as_str()).collect::<Vec<_>>().join(", "))
+		};
+		writeln!(ctx.file, "import {alias}{named} from './{}';", name.to_case(Case::Kebab)).unwrap();
+		declared.extend(enums);
+	}
+	for def in component.enums.iter() {
+		write!(ctx.file, "{}", generate_enum(def)).unwrap();
+	}
 	writeln!(
 		ctx.file,
-		"export default class {component_name} extends Dom.Component<Dom.{root_class}> {{{class_body}}}",
+		"export default class {component_name} extends Dom.Component<{root_class}> {{{class_body}}}",
 	).unwrap();
 
-	ctx.finalize();
+	ctx.finalize()
 }
 
 pub struct CodeGenCtx {
@@ -263,20 +692,159 @@ impl CodeGenCtx {
 		}
 	}
 
-	fn finalize(self) {
+	/// Moves the generated file into place, returning its path.
+	fn finalize(self) -> Option<PathBuf> {
 		std::mem::drop(self.file);
 		let mut path = self.dir;
 		path.push(format!("{}.ts", self.file_name));
 		if path.is_file() {
 			if std::fs::remove_file(&path).is_err() {
-				eprintln!("unable to replace file: {}", path.display());
-				return;
+				Issue::error(format!("unable to replace file: {}", path.display()), Span::internal()).with_code("io").report();
+				return None;
 			}
 		}
 		if std::fs::rename(&self.tempname, &path).is_err() {
-			eprintln!("unable to rename file: {}", self.tempname.display());
-			return;
+			Issue::error(format!("unable to rename file: {}", self.tempname.display()), Span::internal()).with_code("io").report();
+			return None;
 		}
-		println!("{}: {}", "built component".green().bold(), format!("{}", path.display()).bold());
+		Some(path)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::test_util::{generated_code, generated_code_of};
+
+	#[test]
+	fn folds_constant_arithmetic() {
+		let code = generated_code("Rect {\n\tx1: (1px + 2px * 3);\n\tText { content: (7 / 2); }\n}");
+		assert!(code.contains("Dom.Length.px(7)"), "{code}");
+		// Int division truncates, like it does at runtime
+		assert!(code.contains("Dom.Int.from(3)"), "{code}");
+	}
+
+	#[test]
+	fn negation() {
+		let code = generated_code("pub size: Length = 4px;\nRect {\n\tx1: (-(1px + 2px));\n\ty1: (-size / 2);\n}");
+		assert!(code.contains("Dom.Length.px(-3)"), "{code}");
+		assert!(code.contains("size.neg().div(2)"), "{code}");
+	}
+
+	#[test]
+	fn truncates_runtime_int_division() {
+		let code = generated_code("pub a: Int = 7;\npub b: Int = 2;\nRect { Text { content: (a / b); } }");
+		assert!(code.contains("Dom.Int.from(Math.trunc(a.value / b.value))"), "{code}");
+	}
+
+	#[test]
+	fn leaves_division_by_zero_to_the_runtime() {
+		let code = generated_code("Rect {\n\tx1: (1px / 0);\n\ty1: (1px / 0.0);\n}");
+		assert!(!code.contains("inf"), "{code}");
+		assert!(code.contains("Dom.Length.px(1).div(0)"), "{code}");
+	}
+
+	#[test]
+	fn folds_ints_only_within_the_exact_range() {
+		let code = generated_code("Rect { Text { content: (9007199254740990 + 1); } }");
+		assert!(code.contains("Dom.Int.from(9007199254740991)"), "{code}");
+
+		// the runtime can't represent the result exactly, so it isn't folded into an inexact literal
+		let code = generated_code("Rect { Text { content: (9007199254740991 + 1); } }");
+		assert!(code.contains("Dom.Int.from(9007199254740991 + 1)"), "{code}");
+	}
+
+	#[test]
+	fn conditionals() {
+		let code = generated_code("pub active: Boolean;\nRect {\n\tfill: (active ? #fff : #000);\n\tText { content: (true ? \"yes\" : \"no\"); }\n}");
+		assert!(code.contains("connect([this.#bindings.active,], ([active]) => (active.value ? Dom.Brush.rgba(1,1,1,1) : Dom.Brush.rgba(0,0,0,1)))"), "{code}");
+		assert!(code.contains("e.props.content = Dom.String.from(\"yes\");"), "{code}");
+	}
+
+	#[test]
+	fn default_values() {
+		let code = generated_code("pub size: Length = 40px;\npub ratio: Float = 2;\npub count: Int;\nRect {}");
+		assert!(code.contains("size: new Dom.Binding(Dom.Length).set(Dom.Length.px(40)),"), "{code}");
+		assert!(code.contains("ratio: new Dom.Binding(Dom.Float).set(Dom.Float.from(2)),"), "{code}");
+		assert!(code.contains("count: new Dom.Binding(Dom.Int),"), "{code}");
+	}
+
+	#[test]
+	fn nested_assignment_paths() {
+		let code = generated_code_of(&[
+			("app.ui", "import \"./card\";\npub w: Length = 3px;\nRect {\n\tCard { pad.top: 4px; }\n\tCard { pad: { left: (w * 2) }; }\n}"),
+			("card.ui", "struct Pad { top: Length; left: Length; }\npub pad: Pad = { top: 0px, left: 0px };\nRect {}"),
+		]);
+		assert!(code.contains("e.props.pad.top = Dom.Length.px(4);"), "{code}");
+		assert!(code.contains("e.bindings.pad.left.connect([this.#bindings.w,], ([w]) => w.mul(2));"), "{code}");
+	}
+
+	#[test]
+	fn object_properties() {
+		let code = generated_code("struct Point { x: Length; y: Length; }\npub p: Point = { x: 1px, y: 2px };\npub s: Float = 2;\nRect { x1: (p.x * s); }");
+		// every field gets a binding of its own, so fields can be connected to separately
+		assert!(code.contains("x: new Dom.Binding(Dom.Length).set(Dom.Length.px(1)),"), "{code}");
+		assert!(code.contains("y: new Dom.Binding(Dom.Length).set(Dom.Length.px(2)),"), "{code}");
+		assert!(code.contains("connect([this.#bindings.p.x,this.#bindings.s,], ([p$x,s]) => p$x.mul(s.value))"), "{code}");
+	}
+
+	#[test]
+	fn lists() {
+		let code = generated_code("pub xs: [Int] = [1, 2];\npub pick: Int = 0;\nRect {\n\tText { content: (xs[pick]); }\n\tText for x in (xs) { content: (x); }\n}");
+		assert!(code.contains("xs: new Dom.Binding(Dom.List.of(Dom.Int)).set(Dom.List.from(Dom.Int, [Dom.Int.from(1), Dom.Int.from(2), ])),"), "{code}");
+		assert!(code.contains("xs.at(pick.value)"), "{code}");
+		// repeaters iterate the list itself rather than a count
+		assert!(code.contains("(Dom.List.of(Dom.Int), (_$unused_index, x) => {"), "{code}");
+		assert!(code.contains("e.bindings.collection.connect([this.#bindings.xs,], ([xs]) => xs);"), "{code}");
+	}
+
+	#[test]
+	fn enums() {
+		let code = generated_code("enum Align { start, center }\npub a: Align = .center;\nRect {}");
+		assert!(code.contains("export class Align implements Dom.Property {"), "{code}");
+		assert!(code.contains("static Center = new Align();"), "{code}");
+		assert!(code.contains("a: new Dom.Binding(Align).set(Align.Center),"), "{code}");
+	}
+
+	#[test]
+	fn imports_the_enums_of_imported_components() {
+		let card = ("card.ui", "enum Align { start, center }\npub align: Align = .start;\nRect {}");
+		let code = generated_code_of(&[("app.ui", "import \"./card\";\nRect { Card { align: .center; } }"), card]);
+		assert!(code.contains("import Card, { Align } from './card';"), "{code}");
+		assert!(code.contains("e.props.align = Align.Center;"), "{code}");
+
+		// enums are identified by name, so the file's own `Align` isn't imported a second time
+		let code = generated_code_of(&[("app.ui", "import \"./card\";\nenum Align { start, center }\nRect { Card {} }"), card]);
+		assert!(code.contains("import Card from './card';"), "{code}");
+	}
+
+	#[test]
+	fn qualified_enum_paths() {
+		let code = generated_code("enum Align { start, end }\npub layout: Enum.Layout = (Enum.Layout.row);\npub align: Align = (Align.end);\nRect {}");
+		assert!(code.contains("layout: new Dom.Binding(Dom.Enum.Layout).set(Dom.Enum.Layout.Row),"), "{code}");
+		assert!(code.contains("align: new Dom.Binding(Align).set(Align.End),"), "{code}");
+	}
+
+	#[test]
+	fn component_properties() {
+		let code = generated_code_of(&[
+			("app.ui", "import \"./card\";\nRect { Card { thing: { Text { content: \"a\"; } } } }"),
+			("card.ui", "pub thing: Component;\nRect {\n\t(thing)\n}"),
+		]);
+		assert!(code.contains("e.props.thing = (() => {\n\t\t\tlet e = dom.Text();"), "{code}");
+
+		let code = generated_code("pub thing: Component;\nRect {\n\t(thing)\n}");
+		assert!(code.contains("thing: new Dom.Binding(Dom.Component),"), "{code}");
+		assert!(code.contains("e.bindings.component.connect([this.#bindings.thing,], ([thing]) => thing);"), "{code}");
+	}
+
+	#[test]
+	fn imported_components() {
+		let code = generated_code_of(&[
+			("app.ui", "import \"./list\" as Items;\nRect { Items { title: \"a\"; } }"),
+			("list.ui", "pub title: String;\nRect {}"),
+		]);
+		assert!(code.contains("import Items from './list';"), "{code}");
+		assert!(code.contains("let e = new Items(dom);"), "{code}");
+		assert!(code.contains("e.props.title = Dom.String.from(\"a\");"), "{code}");
 	}
 }
@@ -1,9 +1,9 @@
-use std::{fmt};
+use std::{fmt, cell::RefCell};
 use colored::*;
 
 use crate::source_file::Span;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum IssueLevel {
   Warning,
   Error,
@@ -11,92 +11,222 @@ pub enum IssueLevel {
 
 pub struct Issue {
   pub level: IssueLevel,
+  pub code: Option<&'static str>,
   pub span: Span,
   pub message: String,
+  pub labels: Vec<Label>,
+  pub notes: Vec<String>,
+  pub help: Vec<String>,
+}
+
+/// A span related to a diagnostic other than the one it is reported at, with a short explanation.
+#[derive(Debug, Clone)]
+pub struct Label {
+  pub span: Span,
+  pub message: String,
+}
+
+/// An issue as handed out to users of the library, once the compiler is done with it.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+  pub level: IssueLevel,
+  /// the kind of issue (e.g. `type-mismatch` or `unknown-property`), for tools that need to tell
+  /// issues apart without parsing messages
+  pub code: Option<&'static str>,
+  pub message: String,
+  pub primary: Span,
+  pub secondary: Vec<Label>,
+  pub notes: Vec<String>,
+  pub help: Vec<String>,
+}
+
+thread_local! {
+  /// issues reported since the sink was last drained, in the order they were reported
+  static ISSUES: RefCell<Vec<Issue>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Drains the issues reported by every stage of the compiler so far.
+pub fn take_diagnostics() -> Vec<Diagnostic> {
+  ISSUES.with(|issues| issues.take()).into_iter().map(Diagnostic::from).collect()
 }
 
 impl Issue {
+  /// Records the issue so it can be reported together with the rest at the end of the build.
+  pub fn report(self) {
+    ISSUES.with(|issues| issues.borrow_mut().push(self));
+  }
+
   pub fn warning<S: Into<String>>(message: S, span: Span) -> Self {
     Issue {
       level: IssueLevel::Warning,
+      code: None,
       message: message.into(),
       span,
+      labels: Vec::new(),
+      notes: Vec::new(),
+      help: Vec::new(),
     }
   }
   pub fn error<S: Into<String>>(message: S, span: Span) -> Self {
     Issue {
       level: IssueLevel::Error,
+      code: None,
       message: message.into(),
       span,
+      labels: Vec::new(),
+      notes: Vec::new(),
+      help: Vec::new(),
     }
   }
+
+  pub fn with_code(mut self, code: &'static str) -> Self {
+    self.code = Some(code);
+    self
+  }
+
+  /// Points at another location involved in the issue, e.g. the first of two conflicting items.
+  pub fn with_label<S: Into<String>>(mut self, span: &Span, message: S) -> Self {
+    self.labels.push(Label { span: span.clone(), message: message.into() });
+    self
+  }
+
+  pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
+    self.notes.push(note.into());
+    self
+  }
+
+  pub fn with_help<S: Into<String>>(mut self, help: S) -> Self {
+    self.help.push(help.into());
+    self
+  }
 }
 
-impl fmt::Display for Issue {
+impl From<Issue> for Diagnostic {
+  fn from(issue: Issue) -> Self {
+    Diagnostic {
+      level: issue.level,
+      code: issue.code,
+      message: issue.message,
+      primary: issue.span,
+      secondary: issue.labels,
+      notes: issue.notes,
+      help: issue.help,
+    }
+  }
+}
+
+impl fmt::Display for Diagnostic {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let notice = match self.level {
       IssueLevel::Warning => "warning".yellow().bold(),
       IssueLevel::Error => "error".red().bold(),
     };
 
-    write!(f, "{}: {}", notice, self.message.bold())?;
-    
-    if self.span.source_file.internal {
-      return Ok(());
+    if let Some(code) = self.code {
+      write!(f, "{}{}: {}", notice, format!("[{code}]").bold(), self.message.bold())?;
+    } else {
+      write!(f, "{}: {}", notice, self.message.bold())?;
     }
 
-    let lines_start = if self.span.start_line < 3 {
-      1
-    } else {
-      self.span.start_line - 2
-    };
-    let lines_end = if self.span.end_line + 2 >= self.span.source_file.lines.len() {
-      self.span.source_file.lines.len() - 1
+    // every snippet shares the gutter width of the widest line number
+    let labels: Vec<_> = self.secondary.iter().filter(|e| !e.span.source_file.internal).collect();
+    let lines_end = std::iter::once(&self.primary)
+      .filter(|e| !e.source_file.internal)
+      .map(|e| snippet_lines(e, PRIMARY_CONTEXT).1)
+      .chain(labels.iter().map(|e| snippet_lines(&e.span, 0).1))
+      .max();
+    let gutter_width = lines_end.map_or(3, |e| (e as f64).log10().floor() as usize + 4);
+
+    if !self.primary.source_file.internal {
+      self.fmt_snippet(f, &self.primary, None, gutter_width)?;
+    }
+    for label in labels {
+      self.fmt_snippet(f, &label.span, Some(&label.message), gutter_width)?;
+    }
+
+    for note in self.notes.iter() {
+      write!(f, "\n{: >width$}{} {}", "", "= note:".blue().bold(), note, width = gutter_width - 2)?;
+    }
+    for help in self.help.iter() {
+      write!(f, "\n{: >width$}{} {}", "", "= help:".blue().bold(), help, width = gutter_width - 2)?;
+    }
+    Ok(())
+  }
+}
+
+/// lines of context shown around the primary span of a diagnostic
+const PRIMARY_CONTEXT: usize = 2;
+
+/// The first and last line shown for `span` with `context` lines around it.
+fn snippet_lines(span: &Span, context: usize) -> (usize, usize) {
+  let lines_start = span.start_line.saturating_sub(context).max(1);
+  let lines_end = std::cmp::min(span.end_line + context, span.source_file.lines.len() - 1);
+  (lines_start, lines_end)
+}
+
+impl Diagnostic {
+  fn highlight<S: Into<String>>(&self, text: S, primary: bool) -> ColoredString {
+    let text = text.into().bold();
+    if !primary {
+      text.blue()
+    } else if self.level == IssueLevel::Error {
+      text.red()
     } else {
-      self.span.end_line + 2
-    };
+      text.yellow()
+    }
+  }
 
-    let gutter_width = (lines_end as f64).log10().floor() as usize + 4;
+  /// Writes the source lines of `span`, underlined with `^` for the primary span (which also gets
+  /// some context around it) or with `-` and the label's message for secondary ones.
+  fn fmt_snippet(&self, f: &mut fmt::Formatter, span: &Span, label: Option<&str>, gutter_width: usize) -> fmt::Result {
+    let primary = label.is_none();
+    let (lines_start, lines_end) = snippet_lines(span, if primary { PRIMARY_CONTEXT } else { 0 });
 
     write!(f, "\n{:>gutter_width$} {}:{}:{}",
-      " -->".blue().bold(),
-      self.span.source_file.file_path.display(),
-      self.span.start_line,
-      self.span.start_column,
+      if primary { " -->" } else { " :::" }.blue().bold(),
+      span.source_file.file_path.display(),
+      span.start_line,
+      span.start_column,
     )?;
 
-    for i in lines_start..self.span.start_line {
+    // spans are shown within the file's lines, even if one points past its end
+    let last_line = span.source_file.lines.len() - 1;
+    let (start_line, end_line) = (span.start_line.min(last_line), span.end_line.min(last_line));
+
+    for i in lines_start..start_line {
       let gutter = format!("{} | ", i).blue().bold();
-      write!(f, "\n{:>gutter_width$}{}", gutter, self.span.source_file.lines[i])?;
+      write!(f, "\n{:>gutter_width$}{}", gutter, span.source_file.lines[i])?;
     }
 
-    for i in self.span.start_line..=self.span.end_line {
-      let line = &self.span.source_file.lines[i];
+    for i in start_line..=end_line {
+      let line = &span.source_file.lines[i];
 
-      let start = if i == self.span.start_line { self.span.start_column-1 } else { 0 };
-      let end = if i == self.span.end_line { self.span.end_column-1 } else { line.len() };
+      let start = if i == span.start_line { span.start_column.saturating_sub(1).min(line.len()) } else { 0 };
+      let end = if i == span.end_line { span.end_column.saturating_sub(1).clamp(start, line.len()) } else { line.len() };
 
       let before = line[0..start].to_owned();
       let highlight = line[start..end].to_owned();
       let after = line[end..].to_owned();
 
       let highlight_width = highlight.len();
-      let highlight = highlight.bold();
-      let highlight = if self.level == IssueLevel::Error { highlight.red() } else { highlight.yellow() };
+      let highlight = self.highlight(highlight, primary);
 
       let gutter = format!("{} | ", i).blue().bold();
       write!(f, "\n{:>gutter_width$}{}{}{}", gutter, before, highlight, after)?;
 
       let gutter = String::from(" | ").blue().bold();
       let before_width = before.len();
-      let underline = format!("{:^>highlight_width$}", "").bold();
-      let underline = if self.level == IssueLevel::Error { underline.red() } else { underline.yellow() };
-      write!(f, "\n{:>gutter_width$}{: >before_width$}{}", gutter, "", underline)?;
+      let marker = if primary { "^" } else { "-" };
+      let mut underline = marker.repeat(highlight_width);
+      if let (Some(label), true) = (label, i == end_line) {
+        underline = format!("{underline} {label}");
+      }
+      write!(f, "\n{:>gutter_width$}{: >before_width$}{}", gutter, "", self.highlight(underline, primary))?;
     }
 
-    for i in (self.span.end_line+1)..=lines_end {
+    for i in (end_line+1)..=lines_end {
       let gutter = format!("{} | ", i).blue().bold();
-      write!(f, "\n{:>gutter_width$}{}", gutter, self.span.source_file.lines[i])?;
+      write!(f, "\n{:>gutter_width$}{}", gutter, span.source_file.lines[i])?;
     }
 
     Ok(())
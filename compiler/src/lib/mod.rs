@@ -2,10 +2,9 @@
 #![allow(dead_code)]
 
 use colored::*;
-use issue::{Issue};
+use issue::Issue;
 
 use maplit::hashmap;
-use source_file::Span;
 
 use std::{collections::HashMap, rc::Rc};
 
@@ -16,12 +15,19 @@ mod codegen;
 mod source_file;
 mod issue;
 
+pub use issue::{Diagnostic, IssueLevel, Label};
+pub use source_file::{SourceFile, Span};
+
 use checker as chk;
+use parser::Children;
 
 pub struct Module<'a> {
 	builtins: HashMap<String, PropDecl>,
+	context_keys: HashMap<String, Type>,
 	imports: &'a HashMap<String, PathBuf>,
-	props: &'a HashMap<String, PropDecl>,
+	props: HashMap<String, PropDecl>,
+	types: HashMap<String, PropDecl>,
+	slots: Vec<String>,
 	stack: Vec<HashMap<String, PropDecl>>,
 	components: &'a HashMap<PathBuf, chk::Component>,
 }
@@ -33,9 +39,18 @@ pub struct Import {
 	pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct ProvideDecl {
+	pub key: String,
+	pub path: Vec<String>,
+	pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct PropDecl {
 	pub is_pub: bool,
+	pub is_readonly: bool,
+	pub inject: Option<String>,
 	pub name: String,
 	pub prop_type: Type,
 	pub default: Option<Expr>,
@@ -52,6 +67,8 @@ impl PropDecl {
 	fn component(def: ComponentDef) -> Self {
 		PropDecl {
 			is_pub: true,
+			is_readonly: false,
+			inject: None,
 			name: def.name.clone(),
 			prop_type: Type::Component(Rc::new(def)),
 			default: None,
@@ -61,16 +78,42 @@ impl PropDecl {
 	fn module(def: ModuleDef) -> Self {
 		PropDecl {
 			is_pub: true,
+			is_readonly: false,
+			inject: None,
 			name: def.name.clone(),
 			prop_type: Type::Module(Rc::new(def)),
 			default: None,
 			span: Span::internal()
 		}
 	}
+	fn enum_decl(def: Rc<EnumDef>) -> Self {
+		PropDecl {
+			is_pub: true,
+			is_readonly: false,
+			inject: None,
+			name: def.name.clone(),
+			prop_type: Type::EnumDecl(def.clone()),
+			default: None,
+			span: def.span.clone(),
+		}
+	}
+	fn field<S: Into<String>>(name: S, prop_type: Type, span: Span) -> Self {
+		PropDecl {
+			is_pub: true,
+			is_readonly: false,
+			inject: None,
+			name: name.into(),
+			prop_type,
+			default: None,
+			span,
+		}
+	}
 	fn function<S: Into<String>>(name: S, args: Vec<Type>, ret: Box<Type>) -> Self {
 		let name = name.into();
 		PropDecl {
 			is_pub: true,
+			is_readonly: false,
+			inject: None,
 			name: name.clone(),
 			prop_type: Type::Function(args, ret),
 			default: None,
@@ -83,6 +126,7 @@ impl PropDecl {
 pub struct PropDef {
 	prop_type: Type,
 	children: Vec<String>,
+	readonly: bool,
 }
 
 #[derive(Debug)]
@@ -91,6 +135,13 @@ pub struct ComponentDef {
 	props: HashMap<String, PropDef>,
 	container: bool,
 	child_rules: ChildRules,
+	provides: Vec<String>,
+	injects: Vec<String>,
+	slots: Vec<String>,
+	/// whether at most one child element may be passed (`@child`)
+	single_child: bool,
+	/// the events handlers can be attached to, e.g. `pointer.click`
+	events: Vec<String>,
 }
 
 impl PartialEq for ComponentDef {
@@ -99,6 +150,21 @@ impl PartialEq for ComponentDef {
 	}
 }
 
+#[derive(Debug)]
+pub struct EnumDef {
+	name: String,
+	variants: Vec<String>,
+	/// builtin enums live in the runtime's `Enum` namespace rather than in the generated file
+	builtin: bool,
+	span: Span,
+}
+
+impl PartialEq for EnumDef {
+	fn eq(&self, other: &Self) -> bool {
+		self.name == other.name && self.builtin == other.builtin
+	}
+}
+
 #[derive(Debug, Clone)]
 enum ChildRules {
 	Any,
@@ -121,16 +187,27 @@ impl PartialEq for ModuleDef {
 }
 
 fn init_builtins() -> HashMap<String, PropDecl> {
+	let layout_enum = Rc::new(EnumDef {
+		name: "Layout".to_owned(),
+		variants: vec!["row".to_owned(), "column".to_owned()],
+		builtin: true,
+		span: Span::internal(),
+	});
 	hashmap![
 		"Rect".to_owned() => PropDecl::component(ComponentDef {
 			name: "Rect".to_owned(),
 			container: true,
 			child_rules: ChildRules::Any,
+			provides: vec!["FrameSize".into()],
+			injects: vec![],
+			slots: vec![],
+			single_child: false,
+			events: vec!["pointer.click".into()],
 			props: hashmap![
-				"x1".to_owned() => PropDef { prop_type: Type::Length, children: vec![] },
-				"y1".to_owned() => PropDef { prop_type: Type::Length, children: vec![] },
-				"x2".to_owned() => PropDef { prop_type: Type::Length, children: vec![] },
-				"y2".to_owned() => PropDef { prop_type: Type::Length, children: vec![] },
+				"x1".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: false },
+				"y1".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: false },
+				"x2".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: false },
+				"y2".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: false },
 				"scaleToParent".to_owned() => PropDef {
 					prop_type: Type::Float,
 					children: vec![
@@ -138,32 +215,64 @@ fn init_builtins() -> HashMap<String, PropDecl> {
 						"y1".to_owned(),
 						"x2".to_owned(),
 						"y2".to_owned(),
-					]
+					],
+					readonly: false,
 				},
-				"fill".to_owned() => PropDef { prop_type: Type::Brush, children: vec![] },
+				"fill".to_owned() => PropDef { prop_type: Type::Brush, children: vec![], readonly: false },
+				"width".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: true },
+				"height".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: true },
 			],
 		}),
 		"Layout".to_owned() => PropDecl::component(ComponentDef {
 			name: "Layout".to_owned(),
 			container: true,
 			child_rules: ChildRules::AnyOf(vec!["Pane".into()]),
+			provides: vec!["FrameSize".into(), "LayoutInfo".into()],
+			injects: vec![],
+			slots: vec![],
+			single_child: false,
+			events: vec![],
 			props: hashmap![
-				"layout".to_owned() => PropDef { prop_type: Type::EnumLayout, children: vec![] },
-				"padding".to_owned() => PropDef { prop_type: Type::Length, children: vec![] },
+				"layout".to_owned() => PropDef { prop_type: Type::Enum(layout_enum.clone()), children: vec![], readonly: false },
+				"padding".to_owned() => PropDef { prop_type: Type::Length, children: vec![], readonly: false },
 			],
 		}),
 		"Pane".to_owned() => PropDecl::component(ComponentDef {
 			name: "Pane".to_owned(),
 			container: true,
 			child_rules: ChildRules::Any,
+			provides: vec!["FrameSize".into()],
+			injects: vec![],
+			slots: vec![],
+			single_child: false,
+			events: vec![],
 			props: hashmap![],
 		}),
 		"Text".to_owned() => PropDecl::component(ComponentDef {
 			name: "Text".to_owned(),
 			container: false,
 			child_rules: ChildRules::None,
+			provides: vec![],
+			injects: vec![],
+			slots: vec![],
+			single_child: false,
+			events: vec![],
 			props: hashmap![
-				"content".to_owned() => PropDef { prop_type: Type::String, children: vec![] },
+				"content".to_owned() => PropDef { prop_type: Type::String, children: vec![], readonly: false },
+			],
+		}),
+		"Slot".to_owned() => PropDecl::component(ComponentDef {
+			name: "Slot".to_owned(),
+			container: false,
+			child_rules: ChildRules::None,
+			provides: vec![],
+			injects: vec![],
+			slots: vec![],
+			single_child: false,
+			events: vec![],
+			props: hashmap![
+				"component".to_owned() => PropDef { prop_type: Type::Element(None), children: vec![], readonly: false },
+				"insert".to_owned() => PropDef { prop_type: Type::Boolean, children: vec![], readonly: false },
 			],
 		}),
 		"Brush".to_owned() => PropDecl::module(ModuleDef {
@@ -176,6 +285,12 @@ fn init_builtins() -> HashMap<String, PropDecl> {
 				),
 			],
 		}),
+		"Enum".to_owned() => PropDecl::module(ModuleDef {
+			name: "Enum".to_owned(),
+			props: hashmap![
+				"Layout".to_owned() => PropDecl::enum_decl(layout_enum),
+			],
+		}),
 		"Math".to_owned() => PropDecl::module(ModuleDef {
 			name: "Math".to_owned(),
 			props: hashmap! [
@@ -189,21 +304,87 @@ fn init_builtins() -> HashMap<String, PropDecl> {
 	]
 }
 
+/// Context values that containers hand down to their children through `provide()`/`inject()`,
+/// keyed by the name used in `inject(...)` and `provide(...)` declarations.
+fn init_context_keys() -> HashMap<String, Type> {
+	hashmap![
+		"FrameSize".to_owned() => Type::Object(hashmap![
+			"width".to_owned() => PropDecl::field("width", Type::Length, Span::internal()),
+			"height".to_owned() => PropDecl::field("height", Type::Length, Span::internal()),
+		]),
+		"LayoutInfo".to_owned() => Type::Object(hashmap![
+			"itemCount".to_owned() => PropDecl::field("itemCount", Type::Int, Span::internal()),
+			"padding".to_owned() => PropDecl::field("padding", Type::Length, Span::internal()),
+		]),
+	]
+}
+
 impl <'a> Module<'a> {
 	pub fn new(
 		imports: &'a HashMap<String, PathBuf>,
 		components: &'a HashMap<PathBuf, chk::Component>,
-		props: &'a HashMap<String, PropDecl>,
+		props: &HashMap<String, PropDecl>,
 	) -> Self {
 		Self {
 			imports,
-			props,
+			props: props.clone(),
+			types: HashMap::new(),
+			slots: Vec::new(),
 			components,
 			stack: Vec::new(),
 			builtins: init_builtins(),
+			context_keys: init_context_keys(),
+		}
+	}
+
+	/// makes the enums declared in the file nameable in expressions, e.g. `Alignment.center`
+	fn declare_enums(&mut self, enums: &[Rc<EnumDef>]) {
+		for def in enums.iter() {
+			self.types.insert(def.name.clone(), PropDecl::enum_decl(def.clone()));
+		}
+	}
+
+	/// replaces qualified type names like `Enum.Layout` (which the parser can't resolve) with the types they name
+	fn resolve_type(&self, t: &Type, span: &Span) -> Result<Type, ()> {
+		match t {
+			Type::Unresolved(path) => match self.lookup(path, span)? {
+				Some((_, Type::EnumDecl(def))) => Ok(Type::Enum(def)),
+				_ => {
+					let message = format!("unrecognized type: {}", path.join("."));
+					Issue::error(message, span.clone()).with_code("unknown-type").report();
+					Err(())
+				},
+			},
+			Type::List(item_type) => Ok(Type::List(Box::new(self.resolve_type(item_type, span)?))),
+			Type::Element(Some(root_type)) => {
+				let Type::Unresolved(path) = root_type.as_ref() else {
+					return Ok(t.clone());
+				};
+				match self.lookup(path, span)? {
+					Some((_, Type::Component(def))) => Ok(Type::Element(Some(Box::new(Type::Component(def))))),
+					_ => {
+						let message = format!("`{}` is not a component", path.join("."));
+						Issue::error(message, span.clone()).with_code("unknown-component").report();
+						Err(())
+					},
+				}
+			},
+			Type::Object(fields) => {
+				let mut resolved = HashMap::new();
+				for (name, field) in fields.iter() {
+					let prop_type = self.resolve_type(&field.prop_type, &field.span)?;
+					resolved.insert(name.clone(), PropDecl { prop_type, ..field.clone() });
+				}
+				Ok(Type::Object(resolved))
+			},
+			_ => Ok(t.clone()),
 		}
 	}
 
+	fn set_prop_type(&mut self, name: &str, t: Type) {
+		self.props.get_mut(name).unwrap().prop_type = t;
+	}
+
 	fn push_scope(&mut self) {
 		self.stack.push(HashMap::new());
 	}
@@ -222,16 +403,26 @@ impl <'a> Module<'a> {
 				Ctx::Scope(_) => ("in an outer scope", false),
 			};
 			let message = format!("binding `{}` already exists {}", binding, message_part);
+			let previous = match ctx {
+				Ctx::Builtin => None,
+				Ctx::Component => self.props.get(&binding),
+				Ctx::Scope(depth) => self.stack.iter().rev().nth(depth).and_then(|e| e.get(&binding)),
+			};
+			let issue = if fail { Issue::error(message, span.clone()) } else { Issue::warning(message, span.clone()) };
+			let issue = match previous {
+				Some(previous) => issue.with_label(&previous.span, "previously declared here"),
+				None => issue,
+			};
+			issue.with_code("shadowed-binding").report();
 			if fail {
-				eprintln!("{}", Issue::error(message, span.clone()));
 				return Err(());
-			} else {
-				eprintln!("{}", Issue::warning(message, span.clone()));
 			}
 		}
 		let map = self.stack.last_mut().unwrap();
 		map.insert(binding.clone(), PropDecl {
 			is_pub: false,
+			is_readonly: false,
+			inject: None,
 			name: binding,
 			prop_type:
 			t.clone(),
@@ -241,6 +432,13 @@ impl <'a> Module<'a> {
 		Ok(())
 	}
 
+	fn context_key(&self, key: &str, span: &Span) -> Result<&Type, ()> {
+		self.context_keys.get(key).ok_or_else(|| {
+			let message = format!("unknown context key `{}`", key);
+			Issue::error(message, span.clone()).with_code("unknown-context").report();
+		})
+	}
+
 	fn lookup_in_map(&self, path: &[String], map: &HashMap<String, PropDecl>, span: &Span) -> Result<Option<Type>, ()> {
 		let mut map = map;
 		let mut it = path.iter().peekable();
@@ -261,7 +459,7 @@ impl <'a> Module<'a> {
 				map = &def.props;
 			} else {
 				let message = format!("`{}` (type `{}`) has no child properties", prop.name, prop.prop_type.name().cyan());
-				eprintln!("{}", Issue::error(message, span.clone()));
+				Issue::error(message, span.clone()).with_code("unknown-property").report();
 				return Err(());
 			}
 
@@ -270,7 +468,7 @@ impl <'a> Module<'a> {
 				prop
 			} else {
 				let message = format!("property `{}` does not exist", segment);
-				eprintln!("{}", Issue::error(message, span.clone()));
+				Issue::error(message, span.clone()).with_code("unknown-property").report();
 				return Err(());
 			};
 		}
@@ -280,36 +478,84 @@ impl <'a> Module<'a> {
 		if let Some(t) = self.lookup_in_map(path, &self.builtins, span)? {
 			return Ok(Some((Ctx::Builtin, t)))
 		}
+		if let Some(t) = self.lookup_in_map(path, &self.types, span)? {
+			return Ok(Some((Ctx::Builtin, t)))
+		}
 		for (depth, map) in self.stack.iter().rev().enumerate() {
 			if let Some(t) = self.lookup_in_map(path, map, span)? {
 				return Ok(Some((Ctx::Scope(depth), t)))
 			}
 		}
-		if let Some(t) = self.lookup_in_map(path, self.props, span)? {
+		if let Some(t) = self.lookup_in_map(path, &self.props, span)? {
 			return Ok(Some((Ctx::Component, t)))
 		}
 		Ok(None)
 	}
 
+	/// the path and checked component of an element name that refers to an imported .ui file
+	fn get_import(&self, path: &[String]) -> Option<(&PathBuf, &chk::Component)> {
+		if path.len() != 1 {
+			return None;
+		}
+		let import_path = self.imports.get(&path[0])?;
+		Some((import_path, self.components.get(import_path)?))
+	}
+
 	fn get_component_def(&self, element: &parser::Element) -> Result<Rc<ComponentDef>, ()> {
-		let prop = self.lookup(&element.path, &element.name_span)?;
+		self.get_component_def_at(&element.path, &element.name_span)
+	}
+
+	fn get_component_def_at(&self, path: &[String], span: &Span) -> Result<Rc<ComponentDef>, ()> {
+		if let Some((_, component)) = self.get_import(path) {
+			return Ok(Rc::new(imported_component_def(component)));
+		}
+		let prop = self.lookup(path, span)?;
 		if let Some((_, t)) = prop {
 			match t {
 				Type::Component(def) => Ok(def.clone()),
 				_ => {
-					let message = format!("`{}` (type `{}`) is not a component", element.path.join("."), t.name().cyan());
-					eprintln!("{}", Issue::error(message, element.name_span.clone()));
+					let message = format!("`{}` (type `{}`) is not a component", path.join("."), t.name().cyan());
+					Issue::error(message, span.clone()).with_code("unknown-component").report();
 					return Err(());
 				}
 			}
 		} else {
-			let message = format!("`{}`: component not found", element.path.join("."));
-			eprintln!("{}", Issue::error(message, element.name_span.clone()));
+			let message = format!("`{}`: component not found", path.join("."));
+			Issue::error(message, span.clone()).with_code("unknown-component").report();
 			return Err(());
 		}
 	}
 }
 
+/// Describes a component compiled from another .ui file, through which only its `pub`
+/// properties can be assigned.
+fn imported_component_def(component: &chk::Component) -> ComponentDef {
+	let props = component.props
+		.iter()
+		.filter(|prop| prop.is_pub)
+		.map(|prop| {
+			let def = PropDef { prop_type: prop.prop_type.clone(), children: vec![], readonly: prop.is_readonly };
+			(prop.name.clone(), def)
+		})
+		.collect();
+	let child_rules = match &component.children {
+		None => ChildRules::None,
+		Some(Children { filter: Some(filter), .. }) => ChildRules::AnyOf(filter.clone()),
+		Some(..) => ChildRules::Any,
+	};
+	ComponentDef {
+		name: component.name.clone(),
+		props,
+		container: component.children.is_some(),
+		child_rules,
+		provides: component.child_provides.clone(),
+		injects: component.injects.clone(),
+		slots: component.slots.clone(),
+		single_child: component.children.as_ref().is_some_and(|e| e.single),
+		events: component.events.clone(),
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Ctx {
 	Builtin,
@@ -325,21 +571,24 @@ pub enum Type {
 	Brush,
 	String,
 	Boolean,
-	EnumLayout,
+	Enum(Rc<EnumDef>),
+	/// the type of an enum's own name, through which its variants can be named (`Alignment.center`)
+	EnumDecl(Rc<EnumDef>),
+	/// a qualified type name (`Enum.Layout`), resolved by the checker
+	Unresolved(Vec<String>),
 	Iter(Box<Type>),
+	List(Box<Type>),
+	Range,
 	Object(HashMap<String, PropDecl>),
+	/// a component instance held by a property (`Component`), optionally constrained to the
+	/// root type of the component (`Component<Rect>`)
+	Element(Option<Box<Type>>),
 	Component(Rc<ComponentDef>),
 	Module(Rc<ModuleDef>),
 	Function(Vec<Type>, Box<Type>),
 	Callback,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum EnumLayout {
-	Row,
-	Column,
-}
-
 impl Type {
 	fn name(&self) -> String {
 		match self {
@@ -349,16 +598,34 @@ impl Type {
 			Type::Brush => "Brush".to_owned(),
 			Type::String => "String".to_owned(),
 			Type::Boolean => "Boolean".to_owned(),
-			Type::Object(..) => "Object".to_owned(),
+			Type::Enum(def) => def.name.clone(),
+			Type::EnumDecl(def) => format!("enum {}", def.name),
+			Type::Unresolved(path) => path.join("."),
+			Type::List(item_type) => format!("[{}]", item_type.name()),
+			Type::Range => "Range".to_owned(),
+			Type::Object(fields) => {
+				let mut fields: Vec<_> = fields.values().map(|e| format!("{}: {}", e.name, e.prop_type.name())).collect();
+				fields.sort();
+				format!("{{ {} }}", fields.join(", "))
+			},
+			Type::Element(None) => "Component".to_owned(),
+			Type::Element(Some(root_type)) => format!("Component<{}>", root_type.name()),
 			Type::Module(..) => "Module".to_owned(),
 			Type::Component(def) => def.name.clone(),
-			_ => unimplemented!("{:?}", self),
+			Type::Iter(item_type) => format!("Iter<{}>", item_type.name()),
+			Type::Function(args, ret) => {
+				let args: Vec<_> = args.iter().map(|e| e.name()).collect();
+				format!("fn({}) -> {}", args.join(", "), ret.name())
+			},
+			Type::Callback => "Callback".to_owned(),
 		}
 	}
 
 	fn iter_type(&self) -> Option<Type> {
 		match self {
 			Type::Int => Some(Type::Int),
+			Type::List(item_type) => Some(*item_type.clone()),
+			Type::Range => Some(Type::Int),
 			_ => None,
 		}
 	}
@@ -370,6 +637,27 @@ pub struct Expr {
 	span: Span,
 }
 
+impl Expr {
+	/// Projects a single field out of an object-typed expression, so that object values can be
+	/// assigned field by field. Returns `None` for expressions that can't be split up this way.
+	fn field(&self, name: &str) -> Option<Expr> {
+		let value = match &self.value {
+			ExprValue::Object(fields) => return fields.get(name).cloned(),
+			ExprValue::Path(path, ctx) => {
+				let path = path.iter().cloned().chain([name.to_owned()]).collect();
+				ExprValue::Path(path, *ctx)
+			},
+			ExprValue::Conditional(condition, then_expr, else_expr) => ExprValue::Conditional(
+				condition.clone(),
+				Box::new(then_expr.field(name)?),
+				Box::new(else_expr.field(name)?),
+			),
+			_ => return None,
+		};
+		Some(Expr { value, span: self.span.clone() })
+	}
+}
+
 #[derive(Debug, Clone)]
 pub enum ExprValue {
 	Px(f64),
@@ -377,15 +665,96 @@ pub enum ExprValue {
 	Int(i64),
 	Color(f64, f64, f64, f64),
 	String(String),
-	Enum(String, Option<String>),
+	Enum(String, Option<Rc<EnumDef>>),
 	Boolean(bool),
 	Object(HashMap<String, Expr>),
+	List(Vec<Expr>, Option<Type>),
+	Index(Box<Expr>, Box<Expr>),
+	Member(Box<Expr>, String),
+	Match(Box<Expr>, Vec<MatchArm>),
+	/// `start..end` or `start..=end` (when inclusive), with an optional `by step`
+	Range(Box<Expr>, Box<Expr>, bool, Option<Box<Expr>>),
 	Path(Vec<String>, Ctx),
 	Coerce(Box<Expr>, Type),
 	FunctionCall(Box<Expr>, Vec<Expr>),
+	Binary(Box<Expr>, BinaryOp, Box<Expr>, Option<(Type, Type)>),
+	Unary(UnaryOp, Box<Expr>),
+	Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+/// `pattern => value`, where a `None` pattern is the `_` wildcard
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+	pattern: Option<Expr>,
+	value: Expr,
+	span: Span,
 }
 
-use std::{ fs, process, path::PathBuf };
+/// The largest `Int` magnitude the runtime represents exactly (`Number.MAX_SAFE_INTEGER`).
+const MAX_INT: i64 = (1 << 53) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Eq,
+	NotEq,
+	Lt,
+	LtEq,
+	Gt,
+	GtEq,
+	And,
+	Or,
+}
+
+impl BinaryOp {
+	fn precedence(&self) -> u8 {
+		match self {
+			BinaryOp::Or => 1,
+			BinaryOp::And => 2,
+			BinaryOp::Eq | BinaryOp::NotEq => 3,
+			BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq => 4,
+			BinaryOp::Add | BinaryOp::Sub => 5,
+			BinaryOp::Mul | BinaryOp::Div => 6,
+		}
+	}
+
+	fn symbol(&self) -> &'static str {
+		match self {
+			BinaryOp::Add => "+",
+			BinaryOp::Sub => "-",
+			BinaryOp::Mul => "*",
+			BinaryOp::Div => "/",
+			BinaryOp::Eq => "==",
+			BinaryOp::NotEq => "!=",
+			BinaryOp::Lt => "<",
+			BinaryOp::LtEq => "<=",
+			BinaryOp::Gt => ">",
+			BinaryOp::GtEq => ">=",
+			BinaryOp::And => "&&",
+			BinaryOp::Or => "||",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+	Not,
+	Neg,
+}
+
+impl UnaryOp {
+	fn symbol(&self) -> &'static str {
+		match self {
+			UnaryOp::Not => "!",
+			UnaryOp::Neg => "-",
+		}
+	}
+}
+
+use std::{ fs, process, path::{Path, PathBuf} };
 
 fn resolve_ui_import<'a>(
 	path: &PathBuf,
@@ -401,11 +770,8 @@ fn resolve_ui_import<'a>(
 	};
 	let pathbuf = if pathbuf.is_none() || !pathbuf.as_ref().unwrap().is_file() {
 		let message = format!("invalid path specified: {}", path.display());
-		if let Some(span) = span {
-			eprintln!("{}", Issue::error(message, span.clone()));
-		} else {
-			eprintln!("{}", message.bold().red());
-		}
+		let span = span.cloned().unwrap_or_else(Span::internal);
+		Issue::error(message, span).with_code("import").report();
 		return Err(());
 	} else {
 		pathbuf.unwrap()
@@ -451,6 +817,8 @@ fn build_impl<'a>(
 	path: &PathBuf,
 	parse_trees: &HashMap<PathBuf, parser::Component>,
 	components: &'a mut HashMap<PathBuf, chk::Component>,
+	dist: Option<&Path>,
+	generated: &mut Vec<PathBuf>,
 ) -> Result<&'a chk::Component, ()> {
 	use parser::CompileStatus;
 	
@@ -458,7 +826,8 @@ fn build_impl<'a>(
 	match parse_tree.status.get() {
 		CompileStatus::Ready => {},
 		CompileStatus::Building => {
-			eprintln!("encountered recursive import: '{}'", path.display());
+			let message = format!("encountered recursive import: '{}'", path.display());
+			Issue::error(message, Span::internal()).with_code("import").report();
 			return Err(());
 		},
 		CompileStatus::Done => { return Ok(components.get(path).unwrap()); },
@@ -466,33 +835,117 @@ fn build_impl<'a>(
 	parse_tree.status.set(CompileStatus::Building);
 	
 	for (_, path) in parse_tree.imports_map.iter() {
-		build_impl(path, parse_trees, components)?;
+		build_impl(path, parse_trees, components, dist, generated)?;
 	}
 
 	let mut module = Module::new(&parse_tree.imports_map, components, &parse_tree.props);
 	let component = chk::check_component(&mut module, parse_tree)?;
+	// the tree was still checked to report every issue, but no code is generated for it
+	if parse_tree.syntax_errors {
+		return Err(());
+	}
 
-	// let mut dir = path.parent().unwrap().to_path_buf();
-	// dir.push("dist");
-	let dir = PathBuf::from("./dist");
-
-	codegen::generate(&component, dir);
+	if let Some(dist) = dist {
+		generated.extend(codegen::generate(&component, dist));
+	}
 
 	parse_tree.status.set(CompileStatus::Done);
 	components.insert(path.clone(), component);
 	Ok(components.get(path).unwrap())
 }
 
-pub fn build(path: &str) -> Result<Vec<PathBuf>, ()> {
+fn build_all(path: &str, dist: Option<&Path>, generated: &mut Vec<PathBuf>) -> Result<Vec<PathBuf>, ()> {
 	let mut parse_trees = HashMap::new();
 	let path = load_ui_component(&path, &mut parse_trees)?;
 
 	let mut components = HashMap::new();
-	if let Err(_) = build_impl(&path, &parse_trees, &mut components) {
-		eprintln!("{}", "Compliation failed.".bold().red());
-		Err(())
+	build_impl(&path, &parse_trees, &mut components, dist, generated)?;
+	Ok(components.into_iter().map(|(k,_)|k).collect())
+}
+
+/// Everything a build produced, for the caller to present however it needs to.
+pub struct BuildOutput {
+	pub success: bool,
+	/// issues from every stage of the build, in source order
+	pub diagnostics: Vec<Diagnostic>,
+	/// the `.ui` files of the components that were built
+	pub sources: Vec<PathBuf>,
+	/// the files written by code generation, always empty for `check`
+	pub generated: Vec<PathBuf>,
+}
+
+impl BuildOutput {
+	pub fn errors(&self) -> usize {
+		self.diagnostics.iter().filter(|e| e.level == IssueLevel::Error).count()
+	}
+
+	pub fn warnings(&self) -> usize {
+		self.diagnostics.iter().filter(|e| e.level == IssueLevel::Warning).count()
+	}
+}
+
+/// Compiles the component at `path`, writing the generated code to `dist` unless it's `None`.
+fn compile(path: &str, dist: Option<&Path>) -> BuildOutput {
+	let mut generated = Vec::new();
+	let result = build_all(path, dist, &mut generated);
+
+	// every stage reports into the same sink, so all issues are handed out together at the end
+	let mut diagnostics = issue::take_diagnostics();
+	// props are checked in hash map order, so sort to report issues in source order
+	diagnostics.sort_by(|a, b| {
+		let a = (&a.primary.source_file.file_path, a.primary.start_line, a.primary.start_column);
+		let b = (&b.primary.source_file.file_path, b.primary.start_line, b.primary.start_column);
+		a.cmp(&b)
+	});
+	let failed = result.is_err() || diagnostics.iter().any(|e| e.level == IssueLevel::Error);
+
+	BuildOutput {
+		success: !failed,
+		diagnostics,
+		sources: result.unwrap_or_default(),
+		generated,
+	}
+}
+
+/// Checks the component at `path` and everything it imports, and generates code for them.
+pub fn build(path: &str) -> BuildOutput {
+	// let mut dir = path.parent().unwrap().to_path_buf();
+	// dir.push("dist");
+	compile(path, Some(Path::new("./dist")))
+}
+
+/// Checks the component at `path` and everything it imports without generating any code.
+pub fn check(path: &str) -> BuildOutput {
+	compile(path, None)
+}
+
+fn plural(count: usize, word: &str) -> String {
+	if count == 1 {
+		format!("{count} {word}")
 	} else {
-		Ok(components.into_iter().map(|(k,_)|k).collect())
+		format!("{count} {word}s")
+	}
+}
+
+/// Prints the generated files to stdout, and the diagnostics followed by a summary to stderr.
+pub fn report(output: &BuildOutput) {
+	for path in output.generated.iter() {
+		println!("{}: {}", "built component".green().bold(), format!("{}", path.display()).bold());
+	}
+	for diagnostic in output.diagnostics.iter() {
+		eprintln!("{}", diagnostic);
+	}
+
+	let errors = output.errors();
+	let warnings = output.warnings();
+	if !output.success {
+		let mut summary = format!("Compilation failed: {}", plural(errors, "error"));
+		if warnings > 0 {
+			summary = format!("{summary}, {}", plural(warnings, "warning"));
+		}
+		eprintln!("{}", summary.bold().red());
+	} else if warnings > 0 {
+		eprintln!("{}", format!("{} emitted", plural(warnings, "warning")).bold().yellow());
 	}
 }
 
@@ -506,25 +959,25 @@ pub fn watch(path: &str) {
 	let mut prev_paths = Vec::new();
 
 	let mut build_once = || {
-		match build(path) {
-			Ok(paths) => {
-				for path in prev_paths.iter() {
-					watcher.unwatch(path).unwrap();
-				}
-				for path in paths.iter() {
-					watcher.watch(path, RecursiveMode::NonRecursive).unwrap();
-					println!("{}: {}", "watching".blue().bold(), format!("{}", path.display()).bold());
-				}
-				prev_paths = paths;
-				true
-			},
-			Err(_) => {
-				if prev_paths.len() == 0 {
-					watcher.watch(path, RecursiveMode::NonRecursive).unwrap();
-				}
-				println!("{}", "waiting for changes...".dimmed().bold());
-				false
+		let output = build(path);
+		report(&output);
+		if output.success {
+			let paths = output.sources;
+			for path in prev_paths.iter() {
+				watcher.unwatch(path).unwrap();
+			}
+			for path in paths.iter() {
+				watcher.watch(path, RecursiveMode::NonRecursive).unwrap();
+				println!("{}: {}", "watching".blue().bold(), format!("{}", path.display()).bold());
+			}
+			prev_paths = paths;
+			true
+		} else {
+			if prev_paths.len() == 0 {
+				watcher.watch(path, RecursiveMode::NonRecursive).unwrap();
 			}
+			println!("{}", "waiting for changes...".dimmed().bold());
+			false
 		}
 	};
 	
@@ -544,3 +997,110 @@ pub fn watch(path: &str) {
 		}
 	}
 }
+
+/// Helpers for tests that need to run the whole pipeline on `.ui` sources.
+#[cfg(test)]
+mod test_util {
+	use super::*;
+	use convert_case::{Case, Casing};
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Writes `files` into a fresh directory and returns the path of the first one.
+	fn write_files(files: &[(&str, &str)]) -> PathBuf {
+		static COUNT: AtomicUsize = AtomicUsize::new(0);
+		let dir = std::env::temp_dir().join(format!(
+			"ui-compiler-test-{}-{}",
+			process::id(),
+			COUNT.fetch_add(1, Ordering::Relaxed),
+		));
+		fs::create_dir_all(&dir).unwrap();
+		for (name, source) in files {
+			fs::write(dir.join(name), source).unwrap();
+		}
+		dir.join(files[0].0)
+	}
+
+	/// Checks the first of `files`, which may import the others.
+	pub fn check_files(files: &[(&str, &str)]) -> BuildOutput {
+		let path = write_files(files);
+		compile(path.to_str().unwrap(), None)
+	}
+
+	/// Builds a single component and returns the code generated for it.
+	pub fn generated_code(source: &str) -> String {
+		generated_code_of(&[("test.ui", source)])
+	}
+
+	/// Builds the first of `files`, which may import the others, and returns the code generated for it.
+	pub fn generated_code_of(files: &[(&str, &str)]) -> String {
+		let path = write_files(files);
+		let dist = path.parent().unwrap().join("dist");
+		let output = compile(path.to_str().unwrap(), Some(&dist));
+		assert!(output.success, "{:#?}", output.diagnostics);
+		let file_name = path.file_stem().unwrap().to_str().unwrap().to_case(Case::Kebab);
+		fs::read_to_string(dist.join(format!("{file_name}.ts"))).unwrap()
+	}
+
+	/// The codes of the reported diagnostics, in the order they were reported.
+	pub fn codes(output: &BuildOutput) -> Vec<&'static str> {
+		output.diagnostics.iter().map(|d| d.code.unwrap_or("")).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+	use super::{ Diagnostic, IssueLevel, SourceFile, Span, test_util::{check_files, codes} };
+
+	#[test]
+	fn check_hands_out_diagnostics() {
+		let output = check_files(&[("app.ui", "pub n: Int = \"x\";\nRect {\n\tBogus {}\n}")]);
+		assert!(!output.success);
+		assert_eq!((output.errors(), output.warnings()), (2, 0));
+		assert_eq!(codes(&output), ["type-mismatch", "unknown-component"]);
+		assert!(output.generated.is_empty());
+
+		// warnings alone don't fail the build
+		let output = check_files(&[("app.ui", "m: Int = 1;\nRect {}")]);
+		assert!(output.success);
+		assert_eq!(codes(&output), ["unused"]);
+		assert_eq!(output.diagnostics[0].level, IssueLevel::Warning);
+	}
+
+	#[test]
+	fn check_lists_the_checked_sources() {
+		let output = check_files(&[
+			("app.ui", "import \"./card\";\nRect { Card {} }"),
+			("card.ui", "Rect {}"),
+		]);
+		assert!(output.success, "{:#?}", output.diagnostics);
+		let mut names: Vec<_> = output.sources.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+		names.sort();
+		assert_eq!(names, ["app.ui", "card.ui"]);
+		assert!(output.generated.is_empty());
+	}
+
+	#[test]
+	fn renders_spans_at_the_end_of_file() {
+		for (source, location) in [("", "app.ui:1:1"), ("Rect {\n\n", "app.ui:3:1"), ("Rect {\n\tText {}\n\n\n", "app.ui:5:1")] {
+			let output = check_files(&[("app.ui", source)]);
+			assert_eq!(codes(&output), ["parse"]);
+			let rendered = output.diagnostics[0].to_string();
+			assert!(rendered.contains(location), "{rendered}");
+		}
+
+		// spans past the last line are shown on it
+		let source_file = SourceFile::from_source(Path::new("app.ui"), "Rect {".into());
+		let span = Span { start_line: 4, start_column: 3, end_line: 4, end_column: 3, source_file };
+		let diagnostic = Diagnostic {
+			level: IssueLevel::Error,
+			code: None,
+			message: "unexpected end of file".into(),
+			primary: span,
+			secondary: Vec::new(),
+			notes: Vec::new(),
+			help: Vec::new(),
+		};
+		assert!(diagnostic.to_string().contains("Rect {"));
+	}
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::cell::Cell;
 use std::path::PathBuf;
+use std::rc::Rc;
 use convert_case::{Casing, Case};
 use maplit::hashmap;
 
@@ -10,6 +11,11 @@ use crate::{tokens::*, Expr, Ctx};
 
 use super::{
 	Import,
+	ProvideDecl,
+	EnumDef,
+	MatchArm,
+	BinaryOp,
+	UnaryOp,
 	ExprValue,
 	Type,
 	PropDecl,
@@ -40,25 +46,69 @@ pub struct Element {
 	pub path: Vec<String>,
 	pub data: Option<Expr>,
 	pub condition: Option<Condition>,
+	pub else_branch: Option<Box<Element>>,
 	pub repeater: Option<Repeater>,
 	pub props: HashMap<String, PropAsgn>,
+	pub slots: SlotAsgns,
+	pub events: EventAsgns,
 	pub children: Vec<Content>,
 	pub name_span: Span,
 }
 
+/// `name: { Element { ... } }`, filling a named slot of a component
+#[derive(Debug)]
+pub struct SlotAsgn {
+	pub element: Element,
+	pub span: Span,
+}
+
+type SlotAsgns = HashMap<String, SlotAsgn>;
+
+/// `on pointer.click: handler;`
+#[derive(Debug)]
+pub struct EventAsgn {
+	pub handler: Expr,
+	pub span: Span,
+}
+
+type EventAsgns = HashMap<String, EventAsgn>;
+
 impl Element {
-	fn text(value: Expr) -> Self {
+	pub fn text(value: Expr) -> Self {
 		let name_span = value.span.clone();
 		Element {
 			path: vec!["Text".to_owned()],
 			data: None,
 			condition: None,
+			else_branch: None,
 			repeater: None,
+			slots: HashMap::new(),
+			events: HashMap::new(),
 			props: hashmap!["content".to_owned() => PropAsgn { expr: value, span: name_span.clone() } ],
 			children: vec![],
 			name_span,
 		}
 	}
+
+	pub fn slot(value: Expr) -> Self {
+		let name_span = value.span.clone();
+		let insert = Expr { value: ExprValue::Boolean(true), span: name_span.clone() };
+		Element {
+			path: vec!["Slot".to_owned()],
+			data: None,
+			condition: None,
+			else_branch: None,
+			repeater: None,
+			slots: HashMap::new(),
+			events: HashMap::new(),
+			props: hashmap![
+				"component".to_owned() => PropAsgn { expr: value, span: name_span.clone() },
+				"insert".to_owned() => PropAsgn { expr: insert, span: name_span.clone() },
+			],
+			children: vec![],
+			name_span,
+		}
+	}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -73,9 +123,14 @@ pub struct Component {
 	pub name: String,
 	pub root: Element,
 	pub props: HashMap<String, PropDecl>,
+	pub provides: Vec<ProvideDecl>,
+	pub enums: Vec<Rc<EnumDef>>,
+	pub slots: Vec<String>,
 	pub import_decls: Vec<Import>,
 	pub imports_map: HashMap<String, std::path::PathBuf>,
 	pub status: Cell<CompileStatus>,
+	/// whether parts of the file failed to parse and were skipped, see `Content::Error`
+	pub syntax_errors: bool,
 }
 
 #[derive(Debug)]
@@ -89,12 +144,19 @@ struct Property {
 pub struct Children {
 	pub single: bool,
 	pub filter: Option<Vec<String>>,
+	pub span: Span,
 }
 
 #[derive(Debug)]
 pub enum Content {
 	Element(Element),
+	/// `(expr)`, rendered as text or, for component-typed values, in a slot
+	Value(Expr),
 	Children(Children),
+	/// `@name`, where the content of the named slot is placed
+	Slot(String, Span),
+	/// content that failed to parse and was skipped, kept so later stages know something was there
+	Error(Span),
 }
 
 fn hex_to_int(hex: u8) -> u8 {
@@ -156,6 +218,9 @@ struct Parser {
 	tokens: Vec<Token>,
 	offset: usize,
 	failed: bool,
+	types: HashMap<String, Type>,
+	enums: Vec<Rc<EnumDef>>,
+	slots: Vec<String>,
 }
 
 macro_rules! def_token_matchers {
@@ -187,11 +252,18 @@ impl Parser {
 			tokens,
 			offset: 0,
 			failed: false,
+			types: HashMap::new(),
+			enums: Vec::new(),
+			slots: Vec::new(),
 		}
 	}
 
 	fn error<S: Into<String>>(&mut self, message: S, span: &Span) {
-		eprintln!("{}", Issue::error(message, span.clone()));
+		self.report(Issue::error(message, span.clone()));
+	}
+
+	fn report(&mut self, issue: Issue) {
+		issue.with_code("parse").report();
 		self.failed = true;
 	}
 
@@ -238,6 +310,78 @@ impl Parser {
 	def_token_matchers! { permit_number, expect_number, "number", Number, (String, bool, String) }
 	def_token_matchers! { permit_hex_color, expect_hex_color, "hex_color", HexColor, String }
 	def_token_matchers! { permit_enum, expect_enum, "enum", Enum, String }
+	def_token_matchers! { permit_placeholder, expect_placeholder, "placeholder", Placeholder, String }
+
+	/// whether the cursor is at the start of an element, e.g. `Rect {` or `Rect if (...)`
+	fn is_element_start(&self) -> bool {
+		self.cur().is_name() && matches!(self.cur_offset(1).tok, TT::LBrace | TT::If | TT::For)
+	}
+
+	/// whether the current token starts an import, type or property declaration
+	fn is_decl_start(&self) -> bool {
+		matches!(
+			self.cur().tok,
+			TT::Import | TT::Struct | TT::EnumKeyword | TT::Pub | TT::Readonly | TT::Inject | TT::Provide | TT::SlotKeyword
+		)
+	}
+
+	/// Skips the tokens following a syntax error up to a point where parsing can resume: past the
+	/// next `;`, or before the next `}`, element or declaration at the nesting level the error
+	/// occurred at.
+	/// At least one token is skipped if nothing was consumed since `start`, so loops recovering
+	/// this way always make progress.
+	fn synchronize(&mut self, start: usize) {
+		let mut depth = 0;
+		loop {
+			match self.cur().tok {
+				TT::Eof => break,
+				TT::Semicolon if depth == 0 => {
+					self.offset += 1;
+					break;
+				},
+				TT::RBrace if depth == 0 => break,
+				TT::RBrace => depth -= 1,
+				TT::LBrace => depth += 1,
+				_ if depth == 0 && self.offset > start && (self.is_element_start() || self.is_decl_start()) => break,
+				_ => {},
+			}
+			self.offset += 1;
+		}
+		if self.offset == start && !self.cur().is(TT::Eof) {
+			self.offset += 1;
+		}
+	}
+
+	/// Skips the rest of a struct or enum declaration that failed to parse: past the `}` closing
+	/// its body, or up to the next declaration or element if the error occurred before the body.
+	fn synchronize_type_decl(&mut self, start: usize) {
+		let mut depth: i32 = self.tokens[start..self.offset]
+			.iter()
+			.map(|e| match e.tok { TT::LBrace => 1, TT::RBrace => -1, _ => 0 })
+			.sum();
+		loop {
+			match self.cur().tok {
+				TT::Eof => break,
+				TT::LBrace => depth += 1,
+				TT::RBrace if depth <= 1 => {
+					self.offset += 1;
+					break;
+				},
+				TT::RBrace => depth -= 1,
+				TT::Semicolon if depth == 0 => {
+					self.offset += 1;
+					break;
+				},
+				_ if depth == 0 && (self.is_element_start() || self.is_decl_start()) => break,
+				_ if depth == 0 && self.cur().is_name() && self.cur_offset(1).is(TT::Colon) => break,
+				_ => {},
+			}
+			self.offset += 1;
+		}
+		if self.offset == start && !self.cur().is(TT::Eof) {
+			self.offset += 1;
+		}
+	}
 
 	fn expect_separator(&mut self, tok: TT) -> Result<(), ()> {
 		if !(self.cur().is(TT::RBrace) || self.cur().is(TT::RParen) || self.cur().is(TT::RBrack)) {
@@ -247,7 +391,11 @@ impl Parser {
 	}
 
 	fn parse_type(&mut self) -> Result<(Type, Span), ()> {
-		if let Some((name, span)) = self.permit_name() {
+		if self.cur().is_name() && matches!(self.cur_offset(1).tok, TT::Enum(..)) {
+			// qualified names can only refer to types in other scopes, which the checker resolves
+			let (path, span) = self.parse_path()?;
+			Ok((Type::Unresolved(path), span))
+		} else if let Some((name, span)) = self.permit_name() {
 			match name.as_str() {
 				"Int" => Ok((Type::Int, span.clone())),
 				"Float" => Ok((Type::Float, span.clone())),
@@ -256,71 +404,271 @@ impl Parser {
 				"String" => Ok((Type::String, span.clone())),
 				"Boolean" => Ok((Type::Boolean, span.clone())),
 				"Callback" => Ok((Type::Callback, span.clone())),
+				"Component" => {
+					if self.permit(TT::Lt).is_none() {
+						return Ok((Type::Element(None), span.clone()));
+					}
+					let (path, _) = self.parse_path()?;
+					let end_span = self.expect(TT::Gt)?.span;
+					Ok((Type::Element(Some(Box::new(Type::Unresolved(path)))), span.merge(&end_span)))
+				},
+				_ if self.types.contains_key(&name) => Ok((self.types[&name].clone(), span.clone())),
 				_ => {
 					self.error(format!("unrecognized type: {}", name), &span);
 					Err(())
 				}
 			}
+		} else if self.cur().is(TT::LBrace) {
+			self.parse_object_type()
+		} else if let Some(Token { span, .. }) = self.permit(TT::LBrack) {
+			let (item_type, item_span) = self.parse_type()?;
+			let end_span = self.expect(TT::RBrack)?.span;
+			if let Type::Object(..) = item_type {
+				self.error("lists of objects are not supported", &item_span);
+				return Err(());
+			}
+			Ok((Type::List(Box::new(item_type)), span.merge(&end_span)))
 		} else {
-			unimplemented!()
+			self.expected_error("type", &self.cur().clone());
+			Err(())
 		}
 	}
-	
-	fn parse_imports(&mut self) -> Result<Vec<Import>, ()> {
-		let mut imports = Vec::new();
 
-		while let Some(Token { span, .. }) = self.permit(TT::Import) {
-			let path = PathBuf::from(self.expect_string()?.0);
-			let alias = if self.permit(TT::As).is_some() {
-				Some(self.expect_name()?.0)
+	fn parse_object_type(&mut self) -> Result<(Type, Span), ()> {
+		let span = self.expect(TT::LBrace)?.span;
+		let mut fields: HashMap<String, PropDecl> = HashMap::new();
+		while let Some((name, name_span)) = self.permit_name() {
+			self.expect(TT::Colon)?;
+			let (field_type, type_span) = self.parse_type()?;
+			self.expect_separator(TT::Semicolon)?;
+			if let Some(previous) = fields.get(&name) {
+				let message = format!("field `{}` declared more than once", name);
+				let issue = Issue::error(message, name_span.clone()).with_label(&previous.span, "first declared here");
+				self.report(issue);
+				continue;
+			}
+			fields.insert(name.clone(), PropDecl::field(name, field_type, name_span.merge(&type_span)));
+		}
+		let end_span = self.expect(TT::RBrace)?.span;
+		Ok((Type::Object(fields), span.merge(&end_span)))
+	}
+
+	fn parse_enum_variants(&mut self) -> Result<Vec<String>, ()> {
+		let start_span = self.expect(TT::LBrace)?.span;
+		let mut variants = Vec::new();
+		while let Some((variant, span)) = self.permit_name() {
+			if variants.contains(&variant) {
+				self.error(format!("variant `{}` declared more than once", variant), &span);
 			} else {
-				None
-			};
-			let end_span = self.expect(TT::Semicolon)?.span;
+				variants.push(variant);
+			}
+			self.expect_separator(TT::Comma)?;
+		}
+		let end_span = self.expect(TT::RBrace)?.span;
+		if variants.is_empty() {
+			self.error("enums must have at least one variant", &start_span.merge(&end_span));
+			return Err(());
+		}
+		Ok(variants)
+	}
 
-			imports.push(Import { path, alias, span: span.merge(&end_span) });
+	/// Parses a single struct or enum declaration, returning `false` once there are none left.
+	fn parse_type_decl(&mut self) -> Result<bool, ()> {
+		let (kind, span) = if let Some(Token { span, .. }) = self.permit(TT::Struct) {
+			("struct", span)
+		} else if let Some(Token { span, .. }) = self.permit(TT::EnumKeyword) {
+			("enum", span)
+		} else {
+			return Ok(false);
+		};
+		let (name, name_span) = self.expect_name()?;
+		let decl_type = if kind == "struct" {
+			self.parse_object_type()?.0
+		} else {
+			let def = Rc::new(EnumDef {
+				name: name.clone(),
+				variants: self.parse_enum_variants()?,
+				builtin: false,
+				span: span.merge(&name_span),
+			});
+			Type::Enum(def)
+		};
+		if self.types.contains_key(&name) {
+			self.error(format!("type `{}` declared more than once", name), &span.merge(&name_span));
+			return Ok(true);
+		}
+		if let Type::Enum(def) = &decl_type {
+			self.enums.push(def.clone());
 		}
-		Ok(imports)
+		self.types.insert(name, decl_type);
+		Ok(true)
 	}
 
-	fn parse_prop_decls(&mut self) -> Result<HashMap<String, PropDecl>, ()> {
-		let mut props: Vec<PropDecl> = Vec::new();
+	fn parse_type_decls(&mut self) {
+		loop {
+			let offset = self.offset();
+			match self.parse_type_decl() {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(()) => self.synchronize_type_decl(offset),
+			}
+		}
+	}
 
+	fn parse_context_key(&mut self) -> Result<String, ()> {
+		self.expect(TT::LParen)?;
+		let (key, _) = self.expect_name()?;
+		self.expect(TT::RParen)?;
+		Ok(key)
+	}
+	
+	/// Parses a single import, returning `None` once there are none left.
+	fn parse_import(&mut self) -> Result<Option<Import>, ()> {
+		let Some(Token { span, .. }) = self.permit(TT::Import) else {
+			return Ok(None);
+		};
+		let path = PathBuf::from(self.expect_string()?.0);
+		let alias = if self.permit(TT::As).is_some() {
+			Some(self.expect_name()?.0)
+		} else {
+			None
+		};
+		let end_span = self.expect(TT::Semicolon)?.span;
+		Ok(Some(Import { path, alias, span: span.merge(&end_span) }))
+	}
+
+	fn parse_imports(&mut self) -> Vec<Import> {
+		let mut imports = Vec::new();
 		loop {
-			let (is_pub, name, start_span) = if let Some(pub_token) = self.permit(TT::Pub) {
-				(true, self.expect_name()?.0, pub_token.span)
-			} else if self.cur().is_name() && self.cur_offset(1).is(TT::Colon) {
-				let (name, span) = self.expect_name()?;
-				(false, name, span)
-			} else {
-				break;
-			};
+			let offset = self.offset();
+			match self.parse_import() {
+				Ok(Some(import)) => imports.push(import),
+				Ok(None) => break,
+				Err(()) => self.synchronize(offset),
+			}
+		}
+		imports
+	}
 
-			self.expect(TT::Colon)?;
+	/// Parses a single property, slot or provide declaration, returning `false` once there are none left.
+	fn parse_prop_decl(&mut self, props: &mut Vec<PropDecl>, provides: &mut Vec<ProvideDecl>) -> Result<bool, ()> {
+		if self.cur().is(TT::Struct) || self.cur().is(TT::EnumKeyword) {
+			self.error("type declarations must occur before any property declarations", &self.cur().span.clone());
+			// still parsed, so that the declarations after them are too
+			self.parse_type_decls();
+			return Ok(true);
+		}
 
-			let (prop_type, type_span) = self.parse_type()?;
+		if let Some(Token { span, .. }) = self.permit(TT::Provide) {
+			let key = self.parse_context_key()?;
+			let (path, path_span) = self.parse_path()?;
+			self.expect(TT::Semicolon)?;
+			provides.push(ProvideDecl { key, path, span: span.merge(&path_span) });
+			return Ok(true);
+		}
+
+		// `slot name;` declares a public component property that callers fill with content
+		if let Some(Token { span, .. }) = self.permit(TT::SlotKeyword) {
+			let (name, name_span) = self.expect_name()?;
+			self.expect(TT::Semicolon)?;
+			self.slots.push(name.clone());
 			props.push(PropDecl {
-				is_pub,
-				name: name,
-				prop_type,
+				is_pub: true,
+				is_readonly: false,
+				inject: None,
+				name,
+				prop_type: Type::Element(None),
 				default: None,
-				span: start_span.merge(&type_span),
+				span: span.merge(&name_span),
 			});
+			return Ok(true);
+		}
+
+		let inject = if let Some(Token { span, .. }) = self.permit(TT::Inject) {
+			Some((self.parse_context_key()?, span))
+		} else {
+			None
+		};
+		// `pub` and `readonly` can be given in either order
+		let mut pub_token = None;
+		let mut readonly_token = None;
+		let mut first_modifier = None;
+		while inject.is_none() {
+			let token = if pub_token.is_none() && self.cur().is(TT::Pub) {
+				pub_token.insert(self.expect(TT::Pub)?).clone()
+			} else if readonly_token.is_none() && self.cur().is(TT::Readonly) {
+				readonly_token.insert(self.expect(TT::Readonly)?).clone()
+			} else {
+				break;
+			};
+			first_modifier.get_or_insert(token.span);
+		}
+		let is_pub = pub_token.is_some();
+		let is_readonly = readonly_token.is_some();
+		let (inject, name, start_span) = if let Some((key, span)) = inject {
+			(Some(key), self.expect_name()?.0, span)
+		} else if let Some(span) = first_modifier {
+			(None, self.expect_name()?.0, span)
+		} else if self.cur().is_name() && self.cur_offset(1).is(TT::Colon) {
+			let (name, span) = self.expect_name()?;
+			(None, name, span)
+		} else {
+			return Ok(false);
+		};
 
+		self.expect(TT::Colon)?;
 
-			self.expect(TT::Semicolon)?;
+		let (prop_type, type_span) = self.parse_type()?;
+		let default = if self.permit(TT::Equals).is_some() {
+			Some(self.parse_value()?)
+		} else {
+			None
+		};
+		if is_readonly && default.is_none() {
+			self.error(format!("readonly property `{}` must be given a value", name), &start_span.merge(&type_span));
+			return Err(());
+		} else if inject.is_some() && default.is_some() {
+			self.error(format!("injected property `{}` cannot be given a value", name), &start_span.merge(&type_span));
+			return Err(());
 		}
+		props.push(PropDecl {
+			is_pub,
+			is_readonly,
+			inject,
+			name: name,
+			prop_type,
+			default,
+			span: start_span.merge(&type_span),
+		});
+
+		self.expect(TT::Semicolon)?;
+		Ok(true)
+	}
 
-		let map = props.into_iter().fold(HashMap::new(), |mut map, e| {
-			if map.contains_key(&e.name) {
-				self.error(format!("property `{}` declared more than once", e.name), &e.span);
+	fn parse_prop_decls(&mut self) -> Result<(HashMap<String, PropDecl>, Vec<ProvideDecl>), ()> {
+		let mut props: Vec<PropDecl> = Vec::new();
+		let mut provides = Vec::new();
+
+		loop {
+			let offset = self.offset();
+			match self.parse_prop_decl(&mut props, &mut provides) {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(()) => self.synchronize(offset),
+			}
+		}
+
+		let map = props.into_iter().fold(HashMap::new(), |mut map: HashMap<String, PropDecl>, e| {
+			if let Some(previous) = map.get(&e.name) {
+				let message = format!("property `{}` declared more than once", e.name);
+				self.report(Issue::error(message, e.span.clone()).with_label(&previous.span, "first declared here"));
 			} else {
 				map.insert(e.name.clone(), e);
 			}
 			map
 		});
 
-		return Ok(map);
+		return Ok((map, provides));
 	}
 
 	fn parse_path(&mut self) -> Result<(Vec<String>, Span), ()> {
@@ -363,8 +711,97 @@ impl Parser {
 		})
 	}
 
+	fn binary_op(&self) -> Option<BinaryOp> {
+		match self.cur().tok {
+			TT::Plus => Some(BinaryOp::Add),
+			TT::Minus => Some(BinaryOp::Sub),
+			TT::Asterisk => Some(BinaryOp::Mul),
+			TT::Slash => Some(BinaryOp::Div),
+			TT::EqEq => Some(BinaryOp::Eq),
+			TT::NotEq => Some(BinaryOp::NotEq),
+			TT::Lt => Some(BinaryOp::Lt),
+			TT::LtEq => Some(BinaryOp::LtEq),
+			TT::Gt => Some(BinaryOp::Gt),
+			TT::GtEq => Some(BinaryOp::GtEq),
+			TT::AndAnd => Some(BinaryOp::And),
+			TT::OrOr => Some(BinaryOp::Or),
+			_ => None,
+		}
+	}
+
 	fn parse_expr(&mut self) -> Result<Expr, ()> {
-		let paren = self.permit(TT::LParen).is_some();
+		let condition = self.parse_binary_expr(0)?;
+
+		let inclusive = if self.permit(TT::DotDot).is_some() {
+			Some(false)
+		} else if self.permit(TT::DotDotEq).is_some() {
+			Some(true)
+		} else {
+			None
+		};
+		if let Some(inclusive) = inclusive {
+			let start = condition;
+			let end = self.parse_binary_expr(0)?;
+			let step = if self.cur().tok == TT::Name("by".to_owned()) {
+				self.offset += 1;
+				Some(Box::new(self.parse_binary_expr(0)?))
+			} else {
+				None
+			};
+			let span = start.span.merge(step.as_ref().map_or(&end.span, |e| &e.span));
+			return Ok(Expr {
+				value: ExprValue::Range(Box::new(start), Box::new(end), inclusive, step),
+				span,
+			});
+		}
+
+		if self.permit(TT::Question).is_some() {
+			let then_expr = self.parse_expr()?;
+			self.expect(TT::Colon)?;
+			let else_expr = self.parse_expr()?;
+			let span = condition.span.merge(&else_expr.span);
+			Ok(Expr {
+				value: ExprValue::Conditional(Box::new(condition), Box::new(then_expr), Box::new(else_expr)),
+				span,
+			})
+		} else {
+			Ok(condition)
+		}
+	}
+
+	fn parse_binary_expr(&mut self, min_precedence: u8) -> Result<Expr, ()> {
+		let mut lhs = self.parse_primary_expr()?;
+
+		while let Some(op) = self.binary_op() {
+			if op.precedence() < min_precedence {
+				break;
+			}
+			self.offset += 1;
+			let rhs = self.parse_binary_expr(op.precedence() + 1)?;
+			let span = lhs.span.merge(&rhs.span);
+			lhs = Expr {
+				value: ExprValue::Binary(Box::new(lhs), op, Box::new(rhs), None),
+				span,
+			};
+		}
+
+		Ok(lhs)
+	}
+
+	fn parse_primary_expr(&mut self) -> Result<Expr, ()> {
+		if let Some(Token { span, .. }) = self.permit(TT::Bang) {
+			let expr = self.parse_primary_expr()?;
+			let span = span.merge(&expr.span);
+			return Ok(Expr { value: ExprValue::Unary(UnaryOp::Not, Box::new(expr)), span });
+		}
+		// a `-` directly in front of a number is part of the literal, see `parse_value`
+		if self.cur().is(TT::Minus) && !matches!(self.cur_offset(1).tok, TT::Number(..)) {
+			let span = self.cur().span.clone();
+			self.offset += 1;
+			let expr = self.parse_primary_expr()?;
+			let span = span.merge(&expr.span);
+			return Ok(Expr { value: ExprValue::Unary(UnaryOp::Neg, Box::new(expr)), span });
+		}
 
 		let mut expr = if self.cur().is_name() {
 			let (path, span) = self.parse_path()?;
@@ -376,15 +813,19 @@ impl Parser {
 		loop {
 			if self.cur().is(TT::LParen) {
 				expr = self.parse_function_call(expr)?;
+			} else if self.permit(TT::LBrack).is_some() {
+				let index = self.parse_expr()?;
+				let end_span = self.expect(TT::RBrack)?.span;
+				let span = expr.span.merge(&end_span);
+				expr = Expr { value: ExprValue::Index(Box::new(expr), Box::new(index)), span };
+			} else if let Some((name, name_span)) = self.permit_enum() {
+				let span = expr.span.merge(&name_span);
+				expr = Expr { value: ExprValue::Member(Box::new(expr), name), span };
 			} else {
 				break;
 			}
 		}
 
-		if paren {
-			self.expect(TT::RParen)?;
-		}
-
 		Ok(expr)
 	}
 
@@ -403,7 +844,13 @@ impl Parser {
 			match suffix.as_str() {
 				"px" => Ok(Expr { value: ExprValue::Px(n), span }),
 				"" => {
-					let value = if float { ExprValue::Float(n) } else { ExprValue::Int(n as i64) };
+					let value = if float {
+						ExprValue::Float(n)
+					} else {
+						// parsed separately so the checker range-checks the exact value rather than a rounded one
+						let n = num.parse::<i64>().map_or(n as i64, |e| if negative { -e } else { e });
+						ExprValue::Int(n)
+					};
 					Ok(Expr { value, span })
 				},
 				_ => {
@@ -421,65 +868,282 @@ impl Parser {
 			Ok(Expr { value: ExprValue::String(s), span })
 		} else if let Some((s, span)) = self.permit_enum() {
 			Ok(Expr { value: ExprValue::Enum(s, None), span })
-		} else if self.cur().tok == TT::LParen {
-			self.parse_expr()
+		} else if self.permit(TT::LParen).is_some() {
+			let expr = self.parse_expr()?;
+			self.expect(TT::RParen)?;
+			Ok(expr)
+		} else if self.cur().is(TT::LBrace) {
+			self.parse_object()
+		} else if self.cur().is(TT::Match) {
+			self.parse_match()
+		} else if let Some(Token { span, .. }) = self.permit(TT::LBrack) {
+			let mut items = Vec::new();
+			while !self.cur().is(TT::RBrack) {
+				items.push(self.parse_expr()?);
+				self.expect_separator(TT::Comma)?;
+			}
+			let end_span = self.expect(TT::RBrack)?.span;
+			Ok(Expr { value: ExprValue::List(items, None), span: span.merge(&end_span) })
 		} else {
 			self.expected_error("value expression", &self.cur().clone());
 			Err(())
 		}
 	}
 
-	fn parse_prop_assignments(&mut self) -> Result<HashMap<String, PropAsgn>, ()> {
+	/// `@children` or `@child`, optionally filtered by element type (`@children<Pane, Text>`)
+	fn parse_children_placeholder(&mut self, name: String, mut span: Span) -> Result<Children, ()> {
+		let single = name == "child";
+		let filter = if self.permit(TT::Lt).is_some() {
+			let mut filter = Vec::new();
+			loop {
+				let (name, _) = self.expect_name()?;
+				filter.push(name);
+				if self.permit(TT::Comma).is_none() {
+					break;
+				}
+			}
+			span = span.merge(&self.expect(TT::Gt)?.span);
+			Some(filter)
+		} else {
+			None
+		};
+		Ok(Children { single, filter, span })
+	}
+
+	fn parse_match(&mut self) -> Result<Expr, ()> {
+		let span = self.expect(TT::Match)?.span;
+		self.expect(TT::LParen)?;
+		let subject = self.parse_expr()?;
+		self.expect(TT::RParen)?;
+		self.expect(TT::LBrace)?;
+		let mut arms = Vec::new();
+		while !self.cur().is(TT::RBrace) {
+			let (pattern, pattern_span) = if self.cur().tok == TT::Name("_".to_owned()) {
+				(None, self.expect_name()?.1)
+			} else {
+				let pattern = self.parse_primary_expr()?;
+				let span = pattern.span.clone();
+				(Some(pattern), span)
+			};
+			self.expect(TT::FatArrow)?;
+			let value = self.parse_expr()?;
+			let span = pattern_span.merge(&value.span);
+			arms.push(MatchArm { pattern, value, span });
+			self.expect_separator(TT::Comma)?;
+		}
+		let end_span = self.expect(TT::RBrace)?.span;
+		Ok(Expr { value: ExprValue::Match(Box::new(subject), arms), span: span.merge(&end_span) })
+	}
+
+	fn parse_object(&mut self) -> Result<Expr, ()> {
+		let span = self.expect(TT::LBrace)?.span;
+		let mut fields = HashMap::new();
+		while let Some((name, name_span)) = self.permit_name() {
+			self.expect(TT::Colon)?;
+			let value = self.parse_expr()?;
+			self.expect_separator(TT::Comma)?;
+			if fields.contains_key(&name) {
+				self.error(format!("field `{}` assigned more than once", name), &name_span.merge(&value.span));
+				return Err(());
+			}
+			fields.insert(name, value);
+		}
+		let end_span = self.expect(TT::RBrace)?.span;
+		Ok(Expr { value: ExprValue::Object(fields), span: span.merge(&end_span) })
+	}
+
+	/// Whether the `{` at the cursor opens an object literal (`{ a: 1, b: 2 }`) rather than a group
+	/// of property assignments (`{ a: 1; b: 2 }`), going by the first separator inside the braces.
+	fn is_object_literal(&self) -> bool {
+		let mut depth = 0;
+		for tok in self.tokens[self.offset..].iter() {
+			match tok.tok {
+				TT::LBrace | TT::LParen | TT::LBrack => depth += 1,
+				TT::RBrace | TT::RParen | TT::RBrack => {
+					depth -= 1;
+					if depth == 0 {
+						return false;
+					}
+				},
+				TT::Comma if depth == 1 => return true,
+				TT::Semicolon if depth == 1 => return false,
+				TT::Eof => return false,
+				_ => {},
+			}
+		}
+		false
+	}
+
+	fn parse_prop_assignments(&mut self) -> Result<(HashMap<String, PropAsgn>, SlotAsgns, EventAsgns), ()> {
 		let mut props = Vec::new();
-		loop {
-			let offset = self.offset();
+		let mut slots = Vec::new();
+		let mut events = Vec::new();
+		self.parse_prop_assignment_list(&[], &mut props, &mut slots, &mut events);
 
-			if !self.cur().is_name() {
-				break;
+		let mut props_map: HashMap<String, PropAsgn> = HashMap::new();
+		for prop in props.into_iter() {
+			let key = prop.path.join(".");
+			if let Some(previous) = props_map.get(&key) {
+				let message = format!("property `{}` assigned more than once", key);
+				self.report(Issue::error(message, prop.span).with_label(&previous.span, "first assigned here"));
+				continue;
+			}
+			// assigning `a` and `a.b` would make the result depend on the order the assignments are applied in
+			let overlapping = props_map.iter().find(|(e, _)| e.starts_with(&format!("{key}.")) || key.starts_with(&format!("{e}.")));
+			if let Some((other, previous)) = overlapping {
+				let message = format!("property `{}` overlaps with the assignment to `{}`", key, other);
+				let issue = Issue::error(message, prop.span)
+					.with_label(&previous.span, format!("`{other}` assigned here"))
+					.with_help("assign either the whole property or only its fields");
+				self.report(issue);
+				continue;
 			}
+			props_map.insert(key, PropAsgn { expr: prop.value, span: prop.span });
+		}
 
-			let (path, mut full_span) = self.parse_path()?;
+		let mut slots_map: SlotAsgns = HashMap::new();
+		for (name, slot) in slots.into_iter() {
+			let previous = props_map.get(&name).map(|e| &e.span).or(slots_map.get(&name).map(|e| &e.span));
+			if let Some(previous) = previous {
+				let message = format!("property `{}` assigned more than once", name);
+				let issue = Issue::error(message, slot.span.clone()).with_label(previous, "first assigned here");
+				self.report(issue);
+				continue;
+			}
+			slots_map.insert(name, slot);
+		}
 
-			let value = if let Some(_) = self.permit(TT::Colon) {
-				self.parse_value()?
-			} else {
-				self.set_offset(offset);
-				break
-			};
+		let mut events_map: EventAsgns = HashMap::new();
+		for (name, event) in events.into_iter() {
+			if let Some(previous) = events_map.get(&name) {
+				let message = format!("handler for `{}` assigned more than once", name);
+				let issue = Issue::error(message, event.span.clone()).with_label(&previous.span, "first assigned here");
+				self.report(issue);
+				continue;
+			}
+			events_map.insert(name, event);
+		}
+		
+		Ok((props_map, slots_map, events_map))
+	}
+
+	/// whether the cursor is at `on event.name: handler;`, `on` is only a keyword in front of an event name
+	fn is_event_assignment(&self) -> bool {
+		self.cur().tok == TT::Name("on".to_owned())
+			&& self.cur_offset(1).is_name()
+			&& matches!(self.cur_offset(2).tok, TT::Enum(..) | TT::Period | TT::Colon)
+	}
+
+	/// whether the `{` at the cursor opens slot content (an element) rather than grouped assignments
+	fn is_slot_content(&self) -> bool {
+		match self.cur_offset(1).tok {
+			TT::String(..) => true,
+			TT::Name(..) => matches!(self.cur_offset(2).tok, TT::LBrace | TT::If | TT::For),
+			_ => false,
+		}
+	}
 
-			full_span = full_span.merge(&value.span);
+	/// Parses `a.b: value;` assignments, where grouped assignments like `a: { b: value; c: value }`
+	/// are flattened into the same list with `prefix` prepended to their paths.
+	fn parse_prop_assignment_list(
+		&mut self,
+		prefix: &[String],
+		props: &mut Vec<Property>,
+		slots: &mut Vec<(String, SlotAsgn)>,
+		events: &mut Vec<(String, EventAsgn)>,
+	) {
+		loop {
+			let offset = self.offset();
+			match self.parse_prop_assignment(prefix, props, slots, events) {
+				Ok(true) => {},
+				Ok(false) => break,
+				Err(()) => self.synchronize(offset),
+			}
+		}
+	}
 
+	/// Parses a single (possibly grouped) assignment, returning `false` once there are none left.
+	fn parse_prop_assignment(
+		&mut self,
+		prefix: &[String],
+		props: &mut Vec<Property>,
+		slots: &mut Vec<(String, SlotAsgn)>,
+		events: &mut Vec<(String, EventAsgn)>,
+	) -> Result<bool, ()> {
+		let offset = self.offset();
+
+		if self.is_event_assignment() {
+			let span = self.cur().span.clone();
+			self.offset += 1;
+			let (path, _) = self.parse_path()?;
+			if !prefix.is_empty() {
+				self.error("event handlers cannot be grouped with property assignments", &span);
+				return Err(());
+			}
+			self.expect(TT::Colon)?;
+			let (handler_path, handler_span) = self.parse_path()?;
+			let handler = Expr { value: ExprValue::Path(handler_path, Ctx::Component), span: handler_span };
+			let span = span.merge(&handler.span);
 			self.expect_separator(TT::Semicolon)?;
+			events.push((path.join("."), EventAsgn { handler, span }));
+			return Ok(true);
+		}
 
-			props.push(Property {
-				path,
-				value,
-				span: full_span,
-			});
+		if !self.cur().is_name() {
+			return Ok(false);
 		}
 
-		fn add_property(map: &mut HashMap<String, PropAsgn>, path: &[String], prop: &Property) -> Result<(), (String, Span)> {
-			if path.len() == 1 {
-				if map.contains_key(&path[0]) {
-					return Err((format!("property `{}` assigned more than once", path[0]), prop.span.clone()));
-				} else {
-					map.insert(path[0].clone(), PropAsgn { expr: prop.value.clone(), span: prop.span.clone() });
-				}
+		let (path, mut full_span) = self.parse_path()?;
+
+		if self.permit(TT::Colon).is_none() {
+			self.set_offset(offset);
+			return Ok(false);
+		}
+
+		let path = prefix.iter().cloned().chain(path).collect::<Vec<_>>();
+
+		if self.cur().is(TT::LBrace) && self.is_slot_content() {
+			self.expect(TT::LBrace)?;
+			let element = if let Some((value, span)) = self.permit_string() {
+				Element::text(Expr { value: ExprValue::String(value), span })
 			} else {
-				unimplemented!();
+				self.parse_element()?
+			};
+			if !self.cur().is(TT::RBrace) {
+				self.error("slot content must be a single element", &self.cur().span.clone());
+				// skip the extra content so the assignments after it are still parsed
+				while !self.cur().is(TT::RBrace) && !self.cur().is(TT::Eof) {
+					let offset = self.offset();
+					if self.parse_element().is_err() {
+						self.synchronize(offset);
+					}
+				}
 			}
-			Ok(())
+			full_span = full_span.merge(&self.expect(TT::RBrace)?.span);
+			self.permit(TT::Semicolon);
+			slots.push((path.join("."), SlotAsgn { element, span: full_span }));
+			return Ok(true);
 		}
 
-		let mut props_map = HashMap::new();
-		for prop in props.into_iter() {
-			if let Err((message, span)) = add_property(&mut props_map, &prop.path, &prop) {
-				self.error(message, &span);
-				return Err(());
-			}
+		if self.cur().is(TT::LBrace) && !self.is_object_literal() {
+			self.expect(TT::LBrace)?;
+			self.parse_prop_assignment_list(&path, props, slots, events);
+			self.expect(TT::RBrace)?;
+			self.permit(TT::Semicolon);
+			return Ok(true);
 		}
-		
-		Ok(props_map)
+
+		let value = self.parse_value()?;
+		full_span = full_span.merge(&value.span);
+
+		self.expect_separator(TT::Semicolon)?;
+
+		props.push(Property {
+			path,
+			value,
+			span: full_span,
+		});
+		Ok(true)
 	}
 
 	fn parse_element(&mut self) -> Result<Element, ()> {
@@ -520,46 +1184,95 @@ impl Parser {
 		}
 
 		self.expect(TT::LBrace)?;
-		let props = self.parse_prop_assignments()?;
+		let (props, slots, events) = self.parse_prop_assignments()?;
 
 		let mut children = Vec::new();
 		loop {
+			let offset = self.offset();
+			let span = self.cur().span.clone();
 			if self.cur().is_name() {
 				if self.cur_offset(1).is(TT::Colon) {
-					self.error(illegal_prop_message, &self.cur().span.clone());
-					return Err(());
+					self.error(illegal_prop_message, &span);
+					self.synchronize(offset);
+					children.push(Content::Error(span));
+					continue;
+				}
+				match self.parse_element() {
+					Ok(child) => children.push(Content::Element(child)),
+					Err(()) => {
+						self.synchronize(offset);
+						children.push(Content::Error(span));
+					},
 				}
-				let child = self.parse_element()?;
-				children.push(Content::Element(child));
 			} else if let Some((value, span)) = self.permit_string() {
 				let value = Expr { value: ExprValue::String(value), span };
 				children.push(Content::Element(Element::text(value)));
 			} else if self.cur().is(TT::LParen) {
-				let value = self.parse_expr()?;
-				children.push(Content::Element(Element::text(value)));
-			} else if self.cur().is(TT::Pub) {
-				self.error(illegal_prop_message, &self.cur().span.clone());
-				return Err(());
-			} else {
+				match self.parse_value() {
+					Ok(value) => children.push(Content::Value(value)),
+					Err(()) => {
+						self.synchronize(offset);
+						children.push(Content::Error(span));
+					},
+				}
+			} else if let Some((name, span)) = self.permit_placeholder() {
+				if name == "children" || name == "child" {
+					match self.parse_children_placeholder(name, span.clone()) {
+						Ok(c) => children.push(Content::Children(c)),
+						Err(()) => {
+							self.synchronize(offset);
+							children.push(Content::Error(span));
+						},
+					}
+				} else {
+					children.push(Content::Slot(name, span));
+				}
+			} else if [TT::Pub, TT::Readonly, TT::Inject, TT::Provide, TT::Struct, TT::EnumKeyword, TT::SlotKeyword].iter().any(|e| self.cur().is(e.clone())) {
+				self.error(illegal_prop_message, &span);
+				self.synchronize(offset);
+				children.push(Content::Error(span));
+			} else if self.cur().is(TT::Else) {
+				self.error("`else` must follow an element with an `if ...` condition", &span);
+				self.synchronize(offset);
+				children.push(Content::Error(span));
+			} else if self.cur().is(TT::RBrace) || self.cur().is(TT::Eof) {
 				break;
+			} else {
+				self.expected_error("element", &self.cur().clone());
+				self.synchronize(offset);
+				children.push(Content::Error(span));
 			}
 		}
 		self.expect(TT::RBrace)?;
 
+		let else_branch = if condition.is_some() && self.permit(TT::Else).is_some() {
+			let else_branch = self.parse_element()?;
+			if let Some(repeater) = else_branch.repeater.as_ref() {
+				self.error("`for ... in ...` cannot be used on an `else` branch", &repeater.span);
+			}
+			Some(Box::new(else_branch))
+		} else {
+			None
+		};
+
 		Ok(Element {
 			path,
 			data: None,
 			condition,
+			else_branch,
 			repeater,
 			props,
+			slots,
+			events,
 			children,
 			name_span,
 		})
 	}
 
 	pub fn parse(&mut self) -> Result<Component, ()> {
-		let imports = self.parse_imports()?;
-		let props = self.parse_prop_decls()?;
+		let imports = self.parse_imports();
+		self.parse_type_decls();
+		let (props, provides) = self.parse_prop_decls()?;
 		let root = self.parse_element()?;
 
 		if !self.cur().is(TT::Eof) {
@@ -573,15 +1286,12 @@ impl Parser {
 			} else {
 				self.expected_error("end of file", &self.cur().clone());
 			}
-			return Err(());	
 		}
 
 		if let Some(condition) = root.condition.as_ref() {
 			self.error("`if ...` cannot be used on the root of the component", &condition.span);
-			return Err(());
 		} else if let Some(repeater) = root.repeater.as_ref() {
 			self.error("`for ... in ...` cannot be used on the root of the component", &repeater.span);
-			return Err(());
 		}
 
 		let file_stem: String = self.file_path
@@ -591,18 +1301,25 @@ impl Parser {
 			.into();
 		let name = file_stem.to_case(Case::UpperCamel);
 
-		if self.failed {
-			Err(())
-		} else {
-			Ok(Component {
-				name,
-				props,
-				root,
-				import_decls: imports,
-				imports_map: HashMap::new(),
-				status: Cell::new(CompileStatus::Ready),
-			})
+		// enums are exported from the generated file next to the component's own class
+		if let Some(def) = self.enums.iter().find(|e| e.name == name) {
+			let span = def.span.clone();
+			self.error(format!("enum `{}` has the same name as the component", name), &span);
 		}
+
+		// a tree recovered from syntax errors is still handed on, so the checker can report its other issues
+		Ok(Component {
+			name,
+			props,
+			provides,
+			enums: std::mem::take(&mut self.enums),
+			slots: std::mem::take(&mut self.slots),
+			root,
+			import_decls: imports,
+			imports_map: HashMap::new(),
+			status: Cell::new(CompileStatus::Ready),
+			syntax_errors: self.failed,
+		})
 	}
 }
 
@@ -612,3 +1329,271 @@ pub fn parse(file_path: &PathBuf) -> Result<Component, ()> {
 	Parser::new(file_path, tokens).parse()
 }
 
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::issue::{self, Diagnostic};
+
+	fn parse_source(source: &str) -> (Component, Vec<Diagnostic>) {
+		let file_path = PathBuf::from("test.ui");
+		let tokens = Tokenizer::new(SourceFile::from_source(&file_path, source.into())).tokenize().unwrap();
+		let component = Parser::new(&file_path, tokens).parse().unwrap();
+		(component, issue::take_diagnostics())
+	}
+
+	fn parse_ok(source: &str) -> Component {
+		let (component, diagnostics) = parse_source(source);
+		assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+		component
+	}
+
+	fn default_of<'a>(component: &'a Component, name: &str) -> &'a ExprValue {
+		&component.props[name].default.as_ref().unwrap().value
+	}
+
+	fn int(expr: &Expr) -> i64 {
+		match expr.value {
+			ExprValue::Int(n) => n,
+			ref value => panic!("expected an Int, found {:?}", value),
+		}
+	}
+
+	#[test]
+	fn binary_precedence() {
+		let component = parse_ok("a: Int = (1 + 2 * 3);\nb: Int = (1 * 2 - 3);\nc: Int = (-a / 2 - -1);\nRect {}");
+
+		let ExprValue::Binary(lhs, BinaryOp::Add, rhs, _) = default_of(&component, "a") else { panic!() };
+		assert_eq!(int(lhs), 1);
+		let ExprValue::Binary(lhs, BinaryOp::Mul, rhs, _) = &rhs.value else { panic!() };
+		assert_eq!((int(lhs), int(rhs)), (2, 3));
+
+		let ExprValue::Binary(lhs, BinaryOp::Sub, rhs, _) = default_of(&component, "b") else { panic!() };
+		assert_eq!(int(rhs), 3);
+		assert!(matches!(lhs.value, ExprValue::Binary(_, BinaryOp::Mul, _, _)));
+
+		// negation binds tighter than any binary operator, and a negative number is a single literal
+		let ExprValue::Binary(lhs, BinaryOp::Sub, rhs, _) = default_of(&component, "c") else { panic!() };
+		assert_eq!(int(rhs), -1);
+		let ExprValue::Binary(lhs, BinaryOp::Div, _, _) = &lhs.value else { panic!() };
+		assert!(matches!(&lhs.value, ExprValue::Unary(UnaryOp::Neg, e) if matches!(&e.value, ExprValue::Path(p, _) if p == &["a"])));
+	}
+
+	#[test]
+	fn int_literals_are_exact() {
+		let component = parse_ok("a: Int = 9007199254740993;\nRect {}");
+		assert!(matches!(default_of(&component, "a"), ExprValue::Int(9007199254740993)));
+	}
+
+	#[test]
+	fn conditionals_nest_to_the_right() {
+		let component = parse_ok("a: Int = (b ? 1 : c ? 2 : 3 + 4);\nRect {}");
+		let ExprValue::Conditional(condition, then_expr, else_expr) = default_of(&component, "a") else { panic!() };
+		assert!(matches!(&condition.value, ExprValue::Path(path, _) if path == &["b"]));
+		assert_eq!(int(then_expr), 1);
+		let ExprValue::Conditional(_, then_expr, else_expr) = &else_expr.value else { panic!() };
+		assert_eq!(int(then_expr), 2);
+		assert!(matches!(else_expr.value, ExprValue::Binary(_, BinaryOp::Add, _, _)));
+	}
+
+	#[test]
+	fn else_chains() {
+		let component = parse_ok("Rect {\n\tText if (a) {} else Rect if (b) {} else Pane {}\n}");
+		let Content::Element(first) = &component.root.children[0] else { panic!() };
+		assert_eq!(first.path, ["Text"]);
+		assert!(first.condition.is_some());
+
+		let second = first.else_branch.as_ref().unwrap();
+		assert_eq!(second.path, ["Rect"]);
+		assert!(second.condition.is_some());
+
+		let last = second.else_branch.as_ref().unwrap();
+		assert_eq!(last.path, ["Pane"]);
+		assert!(last.condition.is_none());
+		assert!(last.else_branch.is_none());
+	}
+
+	#[test]
+	fn modifiers_in_either_order() {
+		let component = parse_ok("pub readonly a: Int = 1;\nreadonly pub b: Int = 2;\nRect {}");
+		for name in ["a", "b"] {
+			assert!(component.props[name].is_pub && component.props[name].is_readonly);
+		}
+	}
+
+	#[test]
+	fn nested_assignment_paths() {
+		let component = parse_ok("Rect {\n\tpad.top: 1px;\n\tmargin: { top: 2px; left: (w) };\n\tsize: { width: 3px, height: 4px };\n}");
+		let mut keys: Vec<_> = component.root.props.keys().map(|k| k.as_str()).collect();
+		keys.sort();
+		// grouped assignments are split into one assignment per field, object literals are kept whole
+		assert_eq!(keys, ["margin.left", "margin.top", "pad.top", "size"]);
+		assert!(matches!(component.root.props["size"].expr.value, ExprValue::Object(_)));
+	}
+
+	#[test]
+	fn overlapping_assignment_paths() {
+		let (_, diagnostics) = parse_source("Rect {\n\tpad.top: 1px;\n\tpad: { top: 2px };\n}");
+		assert_eq!(diagnostics[0].message, "property `pad.top` assigned more than once");
+
+		let (_, diagnostics) = parse_source("Rect {\n\tpad: (p);\n\tpad.top: 2px;\n}");
+		assert_eq!(diagnostics[0].message, "property `pad.top` overlaps with the assignment to `pad`");
+	}
+
+	#[test]
+	fn structs_and_object_literals() {
+		let component = parse_ok("struct Point { x: Length; y: Length; }\npub p: Point = { x: 1px, y: (a) };\nRect {}");
+		let Type::Object(fields) = &component.props["p"].prop_type else { panic!() };
+		assert_eq!(fields["x"].prop_type, Type::Length);
+		assert_eq!(fields["y"].prop_type, Type::Length);
+		let ExprValue::Object(values) = default_of(&component, "p") else { panic!() };
+		assert!(matches!(values["x"].value, ExprValue::Px(n) if n == 1.0));
+		assert!(matches!(&values["y"].value, ExprValue::Path(path, _) if path == &["a"]));
+	}
+
+	#[test]
+	fn lists() {
+		let component = parse_ok("pub xs: [Int] = [1, 2];\nreadonly x: Int = (xs[i + 1]);\nRect {\n\tText for i, x in (xs) {}\n}");
+		assert_eq!(component.props["xs"].prop_type, Type::List(Box::new(Type::Int)));
+		let ExprValue::List(items, _) = default_of(&component, "xs") else { panic!() };
+		assert_eq!(items.iter().map(int).collect::<Vec<_>>(), [1, 2]);
+
+		let ExprValue::Index(list, index) = default_of(&component, "x") else { panic!() };
+		assert!(matches!(&list.value, ExprValue::Path(path, _) if path == &["xs"]));
+		assert!(matches!(index.value, ExprValue::Binary(_, BinaryOp::Add, _, _)));
+
+		let Content::Element(text) = &component.root.children[0] else { panic!() };
+		let repeater = text.repeater.as_ref().unwrap();
+		assert_eq!(repeater.index.as_ref().unwrap().0, "i");
+		assert_eq!(repeater.item.as_ref().unwrap().0, "x");
+	}
+
+	#[test]
+	fn ranges() {
+		let component = parse_ok("Rect {\n\tPane for i in (0..n) {}\n\tPane for i in (1..=9 by 2) {}\n}");
+		let collections: Vec<_> = component.root.children.iter().map(|child| match child {
+			Content::Element(e) => &e.repeater.as_ref().unwrap().collection.value,
+			_ => panic!(),
+		}).collect();
+
+		let ExprValue::Range(start, end, inclusive, step) = collections[0] else { panic!() };
+		assert_eq!(int(start), 0);
+		assert!(matches!(&end.value, ExprValue::Path(path, _) if path == &["n"]));
+		assert!(!inclusive);
+		assert!(step.is_none());
+
+		let ExprValue::Range(start, end, inclusive, Some(step)) = collections[1] else { panic!() };
+		assert_eq!((int(start), int(end), int(step)), (1, 9, 2));
+		assert!(inclusive);
+	}
+
+	#[test]
+	fn enums() {
+		let component = parse_ok("enum Align { start, center, end }\npub a: Align = .center;\nRect {}");
+		assert_eq!(component.enums[0].variants, ["start", "center", "end"]);
+		assert!(matches!(&component.props["a"].prop_type, Type::Enum(def) if def.name == "Align"));
+		assert!(matches!(default_of(&component, "a"), ExprValue::Enum(name, None) if name == "center"));
+
+		let (_, diagnostics) = parse_source("enum Test { a, b }\nRect {}");
+		assert_eq!(diagnostics[0].message, "enum `Test` has the same name as the component");
+	}
+
+	#[test]
+	fn qualified_enum_paths() {
+		let component = parse_ok("pub layout: Enum.Layout = (Enum.Layout.row);\nRect {}");
+		assert_eq!(component.props["layout"].prop_type, Type::Unresolved(vec!["Enum".to_owned(), "Layout".to_owned()]));
+		assert!(matches!(default_of(&component, "layout"), ExprValue::Path(path, _) if path == &["Enum", "Layout", "row"]));
+	}
+
+	#[test]
+	fn match_arms() {
+		let component = parse_ok("a: Int = 0;\nb: String = (match (a) { 0 => \"zero\", 1 => \"one\", _ => \"many\" });\nRect {}");
+		let ExprValue::Match(scrutinee, arms) = default_of(&component, "b") else { panic!() };
+		assert!(matches!(&scrutinee.value, ExprValue::Path(path, _) if path == &["a"]));
+		assert_eq!(arms.len(), 3);
+		assert_eq!(int(arms[1].pattern.as_ref().unwrap()), 1);
+		assert!(matches!(&arms[1].value.value, ExprValue::String(s) if s == "one"));
+		assert!(arms[2].pattern.is_none());
+	}
+
+	#[test]
+	fn component_properties() {
+		let component = parse_ok("pub thing: Component;\npub box: Component<Rect>;\nRect {\n\t(thing)\n}");
+		assert_eq!(component.props["thing"].prop_type, Type::Element(None));
+		let rect = Type::Unresolved(vec!["Rect".to_owned()]);
+		assert_eq!(component.props["box"].prop_type, Type::Element(Some(Box::new(rect))));
+		assert!(matches!(&component.root.children[0], Content::Value(e) if matches!(&e.value, ExprValue::Path(p, _) if p == &["thing"])));
+	}
+
+	#[test]
+	fn slots() {
+		let component = parse_ok("slot header;\nslot body;\nRect {\n\t@header\n\tCard {\n\t\tfooter: { Text {} }\n\t}\n}");
+		assert_eq!(component.slots, ["header", "body"]);
+		assert!(matches!(component.props["header"].prop_type, Type::Element(None)));
+		assert!(matches!(&component.root.children[0], Content::Slot(name, _) if name == "header"));
+
+		let Content::Element(card) = &component.root.children[1] else { panic!() };
+		assert_eq!(card.slots["footer"].element.path, ["Text"]);
+		assert!(card.props.is_empty());
+	}
+
+	#[test]
+	fn imports() {
+		let component = parse_ok("import \"./card\";\nimport \"./list\" as Items;\nRect {\n\tItems {}\n}");
+		let imports: Vec<_> = component.import_decls.iter().map(|e| (e.path.to_str().unwrap(), e.alias.as_deref())).collect();
+		assert_eq!(imports, [("./card", None), ("./list", Some("Items"))]);
+	}
+
+	#[test]
+	fn events() {
+		let component = parse_ok("Rect {\n\ton pointer.click: handlers.save;\n\ton: 1;\n}");
+		let handler = &component.root.events["pointer.click"].handler;
+		assert!(matches!(&handler.value, ExprValue::Path(path, _) if path == &["handlers", "save"]));
+		// `on` is only a keyword in front of an event path, so it still works as a property name
+		assert_eq!(int(&component.root.props["on"].expr), 1);
+	}
+
+	#[test]
+	fn event_handlers_must_be_paths() {
+		let (_, diagnostics) = parse_source("Rect {\n\ton pointer.click: (save);\n}");
+		assert_eq!(diagnostics.len(), 1);
+	}
+
+	#[test]
+	fn recovers_from_syntax_errors() {
+		let (component, diagnostics) = parse_source("Rect {\n\tx1: (1px + ;\n\ty1: 2px;\n\t5\n\tText {}\n}");
+		assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+		assert!(component.syntax_errors);
+		// the valid parts around the errors are kept
+		assert!(component.root.props.contains_key("y1"));
+		assert!(component.root.children.iter().any(|c| matches!(c, Content::Element(e) if e.path == ["Text"])));
+		assert!(component.root.children.iter().any(|c| matches!(c, Content::Error(_))));
+
+		// bad imports and type declarations are skipped up to the next declaration
+		let (component, diagnostics) = parse_source(
+			"import \"./a\" as ;\nimport \"./b\"\nstruct P { x: ; y: Length; }\nenum E { a, a, b }\n\
+			struct Q { z: Int; z: Int; }\npub q: Int = 1;\nenum F { c }\npub r: F = .c;\nRect { x1: (1px + ); }",
+		);
+		let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+		assert_eq!(messages, [
+			"expected name, found `;`",
+			"expected ;, found `struct`",
+			"expected type, found `;`",
+			"variant `a` declared more than once",
+			"field `z` declared more than once",
+			"type declarations must occur before any property declarations",
+			"expected value expression, found `)`",
+		]);
+		assert_eq!(component.enums.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(), ["E", "F"]);
+		assert!(component.props.contains_key("q") && component.props.contains_key("r"));
+	}
+
+	#[test]
+	fn duplicate_assignments_point_at_the_first() {
+		let (_, diagnostics) = parse_source("Rect {\n\tx1: 1px;\n\tx1: 2px;\n}");
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].message, "property `x1` assigned more than once");
+		assert_eq!(diagnostics[0].secondary[0].message, "first assigned here");
+		assert_eq!(diagnostics[0].secondary[0].span.start_line, 2);
+		assert_eq!(diagnostics[0].primary.start_line, 3);
+	}
+}
@@ -1,4 +1,6 @@
-use std::{path::PathBuf, rc::Rc, fs, io::Read, iter};
+use std::{path::{Path, PathBuf}, rc::Rc, fs, io::Read, iter};
+
+use crate::issue::Issue;
 
 #[derive(Debug)]
 pub struct SourceFile {
@@ -22,22 +24,36 @@ impl SourceFile {
     let mut buf = String::new();
 		fs::File::open(&file_path)
       .or_else(|_| {
-        eprintln!("could not load file: {}", file_path.display());
+        Issue::error(format!("could not load file: {}", file_path.display()), Span::internal()).with_code("io").report();
         Err(())
       })?
       .read_to_string(&mut buf)
       .unwrap();
-    let mut lines: Vec<_> = iter::once("").chain(buf.lines()).map(|s| s.to_owned()).collect();
-    if lines.last() != Some(&String::new()) {
-      lines.push(String::new());
-    }
+    Ok(Self::from_source(file_path, buf))
+  }
 
-    Ok(Rc::new(SourceFile {
-      file_path: file_path.clone(),
+  pub fn from_source(file_path: &Path, buf: String) -> Rc<Self> {
+    // 1-based, including the (possibly empty) line after the last newline where the file ends
+    let lines = iter::once("")
+      .chain(buf.split('\n').map(|e| e.strip_suffix('\r').unwrap_or(e)))
+      .map(|s| s.to_owned())
+      .collect();
+
+    Rc::new(SourceFile {
+      file_path: file_path.to_path_buf(),
       buf,
       lines,
       internal: false,
-    }))
+    })
+  }
+
+  /// Byte offset into `buf` of a (1-based) line and character column. Positions past the end of
+  /// the buffer, like the end of file after trailing blank lines, map to `buf.len()`.
+  pub fn byte_offset(&self, line: usize, column: usize) -> usize {
+    let line_start: usize = self.buf.split_inclusive('\n').take(line.saturating_sub(1)).map(|e| e.len()).sum();
+    let text = self.lines.get(line).map_or("", |e| e.as_str());
+    let column = text.char_indices().nth(column.saturating_sub(1)).map_or(text.len(), |(i, _)| i);
+    (line_start + column).min(self.buf.len())
   }
 }
 
@@ -90,4 +106,11 @@ impl Span {
       }
     }
   }
+
+  /// The byte offsets the span starts and ends at in its source file.
+  pub fn byte_range(&self) -> (usize, usize) {
+    let start = self.source_file.byte_offset(self.start_line, self.start_column);
+    let end = self.source_file.byte_offset(self.end_line, self.end_column);
+    (start, end)
+  }
 }
@@ -18,14 +18,23 @@ pub enum TT {
 	String(String),
 	Number((String, bool, String)),
 	HexColor(String),
+	Placeholder(String),
 	True,
 	False,
 	Pub,
+	Readonly,
+	Inject,
+	Provide,
+	Struct,
+	EnumKeyword,
+	SlotKeyword,
+	Match,
 	Import,
 	As,
 	For,
 	In,
 	If,
+	Else,
 	LBrace,
 	RBrace,
 	LParen,
@@ -34,12 +43,26 @@ pub enum TT {
 	RBrack,
 	Colon,
 	Semicolon,
+	Equals,
 	Plus,
 	Minus,
 	Asterisk,
 	Period,
+	DotDot,
+	DotDotEq,
 	Comma,
 	Slash,
+	Bang,
+	Lt,
+	Gt,
+	LtEq,
+	GtEq,
+	EqEq,
+	NotEq,
+	AndAnd,
+	OrOr,
+	Question,
+	FatArrow,
 	Err(String),
 	Eof
 }
@@ -52,14 +75,23 @@ impl TT {
 			TT::String(..) => "string".to_owned(),
 			TT::Number(..) => "number".to_owned(),
 			TT::HexColor(..) => "hex color".to_owned(),
+			TT::Placeholder(..) => "placeholder".to_owned(),
 			TT::True => "true".to_owned(),
 			TT::False => "false".to_owned(),
 			TT::Pub => "pub".to_owned(),
+			TT::Readonly => "readonly".to_owned(),
+			TT::Inject => "inject".to_owned(),
+			TT::Provide => "provide".to_owned(),
+			TT::Struct => "struct".to_owned(),
+			TT::EnumKeyword => "enum".to_owned(),
+			TT::SlotKeyword => "slot".to_owned(),
+			TT::Match => "match".to_owned(),
 			TT::Import => "import".to_owned(),
 			TT::As => "as".to_owned(),
 			TT::For => "for".to_owned(),
 			TT::In => "in".to_owned(),
 			TT::If => "if".to_owned(),
+			TT::Else => "else".to_owned(),
 			TT::LBrace => "{".to_owned(),
 			TT::RBrace => "}".to_owned(),
 			TT::LParen => "(".to_owned(),
@@ -68,12 +100,26 @@ impl TT {
 			TT::RBrack => "]".to_owned(),
 			TT::Colon => ":".to_owned(),
 			TT::Semicolon => ";".to_owned(),
+			TT::Equals => "=".to_owned(),
 			TT::Plus => "+".to_owned(),
 			TT::Minus => "-".to_owned(),
 			TT::Asterisk => "*".to_owned(),
 			TT::Period => ".".to_owned(),
+			TT::DotDot => "..".to_owned(),
+			TT::DotDotEq => "..=".to_owned(),
 			TT::Comma => ",".to_owned(),
 			TT::Slash => "/".to_owned(),
+			TT::Bang => "!".to_owned(),
+			TT::Lt => "<".to_owned(),
+			TT::Gt => ">".to_owned(),
+			TT::LtEq => "<=".to_owned(),
+			TT::GtEq => ">=".to_owned(),
+			TT::EqEq => "==".to_owned(),
+			TT::NotEq => "!=".to_owned(),
+			TT::AndAnd => "&&".to_owned(),
+			TT::OrOr => "||".to_owned(),
+			TT::Question => "?".to_owned(),
+			TT::FatArrow => "=>".to_owned(),
 			TT::Eof => "end of file".to_owned(),
 			TT::Err(_) => unreachable!(),
 		}
@@ -158,8 +204,17 @@ fn is_name_first(c: char) -> bool {
 
 fn is_op_one(c: char) -> bool {
 	c == '{' || c == '}' || c == '(' || c == ')' ||
+	c == '[' || c == ']' ||
 	c == ':' || c == ';' || c == '+' || c == '-' ||
-	c == '*' || c == ','
+	c == '*' || c == ',' || c == '!' || c == '<' ||
+	c == '>' || c == '?' || c == '='
+}
+
+fn is_op_two(c: char, c2: char) -> bool {
+	(c == '<' || c == '>' || c == '=' || c == '!') && c2 == '=' ||
+	c == '=' && c2 == '>' ||
+	c == '&' && c2 == '&' ||
+	c == '|' && c2 == '|'
 }
 
 impl <'a> Tokenizer<'a> {
@@ -191,7 +246,7 @@ impl <'a> Tokenizer<'a> {
 	}
 
 	fn error<S: Into<String>>(&mut self, message: S, span: Span) {
-		eprintln!("{}", Issue::error(message.into(), span));
+		Issue::error(message.into(), span).with_code("lex").report();
 		self.failed = true;
 	}
 
@@ -305,12 +360,32 @@ impl <'a> Tokenizer<'a> {
 			'}' => Token { tok: TT::RBrace, span },
 			'(' => Token { tok: TT::LParen, span },
 			')' => Token { tok: TT::RParen, span },
+			'[' => Token { tok: TT::LBrack, span },
+			']' => Token { tok: TT::RBrack, span },
 			':' => Token { tok: TT::Colon, span },
 			';' => Token { tok: TT::Semicolon, span },
 			'+' => Token { tok: TT::Plus, span },
 			'-' => Token { tok: TT::Minus, span },
 			'*' => Token { tok: TT::Asterisk, span },
 			',' => Token { tok: TT::Comma, span },
+			'!' => Token { tok: TT::Bang, span },
+			'<' => Token { tok: TT::Lt, span },
+			'>' => Token { tok: TT::Gt, span },
+			'?' => Token { tok: TT::Question, span },
+			'=' => Token { tok: TT::Equals, span },
+			_ => unreachable!()
+		}
+	}
+
+	fn op_two_token(&mut self, c: char, c2: char, span: Span) -> Token {
+		match (c, c2) {
+			('<', _) => Token { tok: TT::LtEq, span },
+			('>', _) => Token { tok: TT::GtEq, span },
+			('=', '>') => Token { tok: TT::FatArrow, span },
+			('=', _) => Token { tok: TT::EqEq, span },
+			('!', _) => Token { tok: TT::NotEq, span },
+			('&', _) => Token { tok: TT::AndAnd, span },
+			('|', _) => Token { tok: TT::OrOr, span },
 			_ => unreachable!()
 		}
 	}
@@ -320,11 +395,19 @@ impl <'a> Tokenizer<'a> {
 			"true" => Token { tok: TT::True, span },
 			"false" => Token { tok: TT::False, span },
 			"pub" => Token { tok: TT::Pub, span },
+			"readonly" => Token { tok: TT::Readonly, span },
+			"inject" => Token { tok: TT::Inject, span },
+			"provide" => Token { tok: TT::Provide, span },
+			"struct" => Token { tok: TT::Struct, span },
+			"enum" => Token { tok: TT::EnumKeyword, span },
+			"slot" => Token { tok: TT::SlotKeyword, span },
+			"match" => Token { tok: TT::Match, span },
 			"import" => Token { tok: TT::Import, span },
 			"as" => Token { tok: TT::As, span },
 			"for" => Token { tok: TT::For, span },
 			"in" => Token { tok: TT::In, span },
 			"if" => Token { tok: TT::If, span },
+			"else" => Token { tok: TT::Else, span },
 			_ => Token { tok: TT::Name(data), span }
 		}
 	}
@@ -357,7 +440,9 @@ impl <'a> Iterator for Tokenizer<'a> {
 			} else if is_digit(c) {
 				let (mut num, mut span) = self.consume(is_digit);
 				let maybe_dot = self.input.chars().nth(0);
-				let float = if maybe_dot == Some('.') {
+				// `1..5` is a range, not a decimal point
+				let is_range = self.input.chars().nth(1) == Some('.');
+				let float = if maybe_dot == Some('.') && !is_range {
 					let (_, dot_span) = self.consume_single_char();
 					let (num2, span2) = self.consume(is_digit);
 					if num2 == "" {
@@ -377,6 +462,10 @@ impl <'a> Iterator for Tokenizer<'a> {
 				let (_, hash_span) = self.consume_single_char();
 				let (hex, hex_span) = self.consume(is_hex_digit);
 				return Some(self.hex_color_token(hex, hash_span.merge(&hex_span)));
+			} else if c == '@' && is_name_first(c2.unwrap_or('\u{00}')) {
+				let (_, at_span) = self.consume_single_char();
+				let (name, name_span) = self.consume(is_name);
+				return Some(Token { tok: TT::Placeholder(name), span: at_span.merge(&name_span) });
 			} else if c == '"' {
 				let (_, quote1_span) = self.consume_single_char();
 				let (content, content_span) = self.consume(|c| c != '"');
@@ -396,10 +485,22 @@ impl <'a> Iterator for Tokenizer<'a> {
 					let (_, span) = self.consume_single_char();
 					return Some(Token { tok: TT::Slash, span });
 				}
+			} else if c == '.' && c2 == Some('.') {
+				let (_, span1) = self.consume_single_char();
+				let (_, span2) = self.consume_single_char();
+				if self.input.chars().nth(0) == Some('=') {
+					let (_, span3) = self.consume_single_char();
+					return Some(Token { tok: TT::DotDotEq, span: span1.merge(&span3) });
+				}
+				return Some(Token { tok: TT::DotDot, span: span1.merge(&span2) });
 			} else if c == '.' && is_name_first(c2.unwrap_or('\u{00}')) {
 				let (_, dot_span) = self.consume_single_char();
 				let (name, name_span) = self.consume(is_name);
 				return Some(Token { tok: TT::Enum(name), span: dot_span.merge(&name_span) });
+			} else if is_op_two(c, c2.unwrap_or('\u{00}')) {
+				let (c, span1) = self.consume_single_char();
+				let (c2, span2) = self.consume_single_char();
+				return Some(self.op_two_token(c, c2, span1.merge(&span2)));
 			} else if is_op_one(c) {
 				let (c, span) = self.consume_single_char();
 				return Some(self.op_one_token(c, span));
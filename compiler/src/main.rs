@@ -1,10 +1,19 @@
 use ui_compiler::*;
-use std::process;
+use std::{process, io::IsTerminal};
+
+#[derive(Default, PartialEq)]
+enum MessageFormat {
+	#[default]
+	Human,
+	Json,
+}
 
 #[derive(Default)]
 struct Options {
 	file: String,
 	watch: bool,
+	check: bool,
+	message_format: MessageFormat,
 }
 
 fn process_args() -> Options {
@@ -12,6 +21,8 @@ fn process_args() -> Options {
 	let exe = args.next().unwrap();
 	let mut file = None;
 	let mut watch = None;
+	let mut check = None;
+	let mut message_format = None;
 	let mut fail = false;
 
 	for arg in args {
@@ -22,8 +33,20 @@ fn process_args() -> Options {
 				}
 				watch = Some(true);
 			},
+			"--check" =>  {
+				if check.is_some() {
+					fail = true;
+				}
+				check = Some(true);
+			},
+			"--message-format=human" | "--message-format=json" => {
+				if message_format.is_some() {
+					fail = true;
+				}
+				message_format = Some(if arg.ends_with("json") { MessageFormat::Json } else { MessageFormat::Human });
+			},
 			_ => {
-				if file.is_some() {
+				if file.is_some() || arg.starts_with("--") {
 					fail = true;
 				}
 				file = Some(arg);
@@ -31,22 +54,154 @@ fn process_args() -> Options {
 		}
 	}
 
-	if fail || file.is_none() {
-		eprintln!("usage: {} FILE [--watch]", exe);
+	let json = message_format == Some(MessageFormat::Json);
+	if fail || file.is_none() || (watch.is_some() && (check.is_some() || json)) {
+		eprintln!("usage: {} FILE [--watch | --check] [--message-format=human|json]", exe);
 		process::exit(1);
 	}
 
 	Options {
 		file: file.unwrap(),
 		watch: watch.unwrap_or_default(),
+		check: check.unwrap_or_default(),
+		message_format: message_format.unwrap_or_default(),
 	}
 }
 
+/// Diagnostics are written to stderr, but `colored` only checks whether stdout is a terminal.
+fn configure_color() {
+	let forced = std::env::var("CLICOLOR_FORCE").is_ok_and(|e| e != "0");
+	let no_color = std::env::var("NO_COLOR").is_ok_and(|e| !e.is_empty());
+	if !forced && (no_color || !std::io::stderr().is_terminal()) {
+		colored::control::set_override(false);
+	}
+}
+
+fn json_string(s: &str) -> String {
+	let mut out = String::from("\"");
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+	out
+}
+
+fn json_span(span: &Span, is_primary: bool, label: Option<&str>) -> String {
+	let (byte_start, byte_end) = span.byte_range();
+	format!(
+		"{{\"file\":{},\"byte_start\":{},\"byte_end\":{},\"line_start\":{},\"column_start\":{},\"line_end\":{},\"column_end\":{},\"is_primary\":{},\"label\":{}}}",
+		json_string(&span.source_file.file_path.to_string_lossy()),
+		byte_start,
+		byte_end,
+		span.start_line,
+		span.start_column,
+		span.end_line,
+		span.end_column,
+		is_primary,
+		label.map_or("null".to_owned(), json_string),
+	)
+}
+
+/// One JSON object per line: a `diagnostic` for each issue, followed by the build `summary`.
+fn report_json(output: &BuildOutput) {
+	for line in json_lines(output) {
+		println!("{}", line);
+	}
+}
+
+fn json_lines(output: &BuildOutput) -> Vec<String> {
+	let mut lines = Vec::new();
+	for diagnostic in output.diagnostics.iter() {
+		// issues that aren't tied to a source location, like missing files, have no spans
+		let primary = Some(&diagnostic.primary)
+			.filter(|e| !e.source_file.internal)
+			.map(|e| json_span(e, true, None));
+		let secondary = diagnostic.secondary
+			.iter()
+			.filter(|e| !e.span.source_file.internal)
+			.map(|e| json_span(&e.span, false, Some(&e.message)));
+		let spans: Vec<_> = primary.into_iter().chain(secondary).collect();
+		let notes: Vec<_> = diagnostic.notes.iter().map(|e| json_string(e)).collect();
+		let help: Vec<_> = diagnostic.help.iter().map(|e| json_string(e)).collect();
+		let level = match diagnostic.level {
+			IssueLevel::Error => "error",
+			IssueLevel::Warning => "warning",
+		};
+		lines.push(format!(
+			"{{\"type\":\"diagnostic\",\"level\":\"{}\",\"code\":{},\"message\":{},\"spans\":[{}],\"notes\":[{}],\"help\":[{}]}}",
+			level,
+			diagnostic.code.map_or("null".to_owned(), json_string),
+			json_string(&diagnostic.message),
+			spans.join(","),
+			notes.join(","),
+			help.join(","),
+		));
+	}
+	let generated: Vec<_> = output.generated.iter().map(|e| json_string(&e.to_string_lossy())).collect();
+	lines.push(format!(
+		"{{\"type\":\"summary\",\"success\":{},\"errors\":{},\"warnings\":{},\"generated\":[{}]}}",
+		output.success,
+		output.errors(),
+		output.warnings(),
+		generated.join(","),
+	));
+	lines
+}
+
 fn main() {
 	let options = process_args();
+	if options.message_format == MessageFormat::Json {
+		// messages embed styled type names, which tools reading the JSON shouldn't have to strip
+		colored::control::set_override(false);
+	} else {
+		configure_color();
+	}
 	if options.watch {
 		watch(&options.file);
-	} else if let Err(_) = build(&options.file) {
+		return;
+	}
+	let output = if options.check {
+		check(&options.file)
+	} else {
+		build(&options.file)
+	};
+	match options.message_format {
+		MessageFormat::Human => report(&output),
+		MessageFormat::Json => report_json(&output),
+	}
+	if !output.success {
 		process::exit(1);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check_source(name: &str, source: &str) -> Vec<String> {
+		let dir = std::env::temp_dir().join(format!("ui-json-{}", process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let path = dir.join(name);
+		std::fs::write(&path, source).unwrap();
+		json_lines(&check(&path.to_string_lossy()))
+	}
+
+	#[test]
+	fn spans_at_the_end_of_file() {
+		for (name, source) in [("Empty.ui", ""), ("Unterminated.ui", "Rect {\n\n"), ("Blank.ui", "Rect {\n\tText {}\n\n\n")] {
+			let lines = check_source(name, source);
+			assert!(lines[0].contains("\"type\":\"diagnostic\""), "{:?}", lines);
+			let end = format!("\"byte_start\":{},\"byte_end\":{}", source.len(), source.len());
+			assert!(lines[0].contains(&end), "{:?}", lines);
+			assert!(lines.last().unwrap().contains("\"success\":false"));
+		}
+	}
+}
@@ -1,4 +1,5 @@
 import { Collection, Property } from "./common";
+import { PropertyConstructor } from "./binding";
 
 abstract class ScalarValueProperty<T> implements Property {
   constructor(readonly value: T) {}
@@ -20,10 +21,11 @@ export class Int extends ScalarValueProperty<number> implements Collection<Int>
       return Int.from(f.value);
     }
   }
-  constructor(value: number) { super(value << 0); }
+  constructor(value: number) { super(Number.isFinite(value) ? Math.trunc(value) : 0); }
   interpolate(next: Int, fac: number) {
     return Int.from((next.value - this.value) * fac + this.value) as this;
   }
+  neg() { return Int.from(-this.value); }
   *iter() {
     for(let i = 1; i <= this.value; i++) {
       yield Int.from(i);
@@ -52,6 +54,7 @@ export class Float extends ScalarValueProperty<number> {
   interpolate(next: Float, fac: number) {
     return Float.from((next.value - this.value) * fac + this.value) as this;
   }
+  neg() { return Float.from(-this.value); }
 }
 
 export class Boolean extends ScalarValueProperty<boolean> {
@@ -98,3 +101,88 @@ export class String extends ScalarValueProperty<string> {
       return next;
   }
 }
+
+export class Callback extends ScalarValueProperty<() => unknown> {
+  static from(value: () => unknown) { return new Callback(value); };
+  static default() { return new this(() => {}); }
+  static coerce(e: any) {
+    if(e instanceof Callback) {
+      return e;
+    } else if(typeof e === 'function') {
+      return Callback.from(e);
+    } else {
+      return Callback.default();
+    }
+  }
+  constructor(value: () => unknown) { super(value); }
+  call() { return this.value(); }
+  interpolate(next: this, _fac: number): this {
+      return next;
+  }
+}
+
+export class List<P extends Property> implements Collection<P> {
+  static of<P extends Property>(init: PropertyConstructor<P>): PropertyConstructor<List<P>> {
+    return {
+      default: () => new List(init, []),
+      coerce: (e: any) => {
+        if(e instanceof List) {
+          return e;
+        } else if(Array.isArray(e)) {
+          return List.from(init, e);
+        } else {
+          return new List(init, []);
+        }
+      },
+    };
+  }
+  static from<P extends Property>(init: PropertyConstructor<P>, items: any[]) {
+    return new List(init, items.map(e => init.coerce(e)));
+  }
+  constructor(private init: PropertyConstructor<P>, readonly items: readonly P[]) {}
+  get len() { return Int.from(this.items.length); }
+  at(index: number): P {
+    return this.items[index] ?? this.init.default();
+  }
+  equals(other: this) {
+    return other instanceof List &&
+      this.items.length === other.items.length &&
+      this.items.every((e, i) => e.equals(other.items[i]));
+  }
+  interpolate(next: this, _fac: number): this {
+    return next;
+  }
+  *iter() {
+    yield* this.items;
+  }
+}
+
+export class Range implements Collection<Int> {
+  static from(start: number, end: number, inclusive = false, step = 1) {
+    return new Range(start, end, inclusive, step);
+  }
+  static default() { return new Range(0, 0, false, 1); }
+  static coerce(e: any) {
+    return e instanceof Range ? e : Range.default();
+  }
+  constructor(readonly start: number, readonly end: number, readonly inclusive: boolean, readonly step: number) {}
+  equals(other: this) {
+    return other instanceof Range &&
+      this.start === other.start &&
+      this.end === other.end &&
+      this.inclusive === other.inclusive &&
+      this.step === other.step;
+  }
+  interpolate(next: this, _fac: number): this {
+    return next;
+  }
+  *iter() {
+    if(this.step === 0) {
+      return;
+    }
+    const before = (i: number) => this.step > 0 ? i < this.end : i > this.end;
+    for(let i = this.start; before(i) || (this.inclusive && i === this.end); i += this.step) {
+      yield Int.from(i);
+    }
+  }
+}